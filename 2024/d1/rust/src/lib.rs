@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use aoc_common::parse::{numbered_lines, parse_token};
+use aoc_common::{Answer, Params, ParseError, Solution};
+
+pub mod report;
+
+/// The location lists side by side, one column per list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lists {
+    pub columns: Vec<Vec<i64>>,
+}
+
+impl Lists {
+    /// The two lists the puzzle compares; missing ones are empty.
+    pub fn pair(&self) -> (&[i64], &[i64]) {
+        let column = |index: usize| self.columns.get(index).map_or(&[][..], Vec::as_slice);
+        (column(0), column(1))
+    }
+}
+
+/// Reads whitespace separated columns of numbers. The first line decides
+/// how many columns there are; lines that don't match it are skipped, or
+/// reported when `strict`.
+fn read_lists(input: &str, strict: bool) -> Result<Lists, ParseError> {
+    let mut columns: Vec<Vec<i64>> = Vec::new();
+
+    for (line_number, line) in numbered_lines(input) {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty() {
+            continue;
+        }
+        let numbers = tokens
+            .iter()
+            .map(|token| parse_token(line_number, line, token, "location ID"))
+            .collect::<Result<Vec<i64>, _>>();
+        let numbers = match numbers {
+            Ok(numbers) => numbers,
+            Err(e) if strict => return Err(e),
+            Err(_) => continue,
+        };
+
+        if columns.is_empty() {
+            columns = vec![Vec::new(); numbers.len()];
+        }
+        if numbers.len() != columns.len() {
+            if strict {
+                let expected = format!("{} location IDs", columns.len());
+                return Err(ParseError::expected(line_number, 1, expected, line));
+            }
+            continue;
+        }
+        for (column, number) in columns.iter_mut().zip(numbers) {
+            column.push(number);
+        }
+    }
+
+    Ok(Lists { columns })
+}
+
+fn sorted(list: &[i64]) -> Vec<i64> {
+    let mut list = list.to_vec();
+    list.sort_unstable();
+    list
+}
+
+/// How often each number occurs in the list.
+fn frequencies(list: &[i64]) -> HashMap<i64, i64> {
+    let mut counts = HashMap::new();
+    for &number in list {
+        *counts.entry(number).or_insert(0) += 1;
+    }
+    counts
+}
+
+// IDs span all of i64, so distances are u64 and sums i128
+fn first_task(lists: &Lists) -> i128 {
+    let (left, right) = lists.pair();
+
+    sorted(left)
+        .iter()
+        .zip(sorted(right).iter())
+        .map(|(l, r)| i128::from(l.abs_diff(*r)))
+        .sum()
+}
+
+fn second_task(lists: &Lists) -> i128 {
+    let (left, right) = lists.pair();
+    let counts = frequencies(right);
+
+    left.iter()
+        .map(|l| i128::from(*l) * i128::from(counts.get(l).copied().unwrap_or(0)))
+        .sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Lists;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_lists(input, false)
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        read_lists(input, params.is_strict())
+    }
+
+    fn part1(lists: &Self::Input) -> Answer {
+        first_task(lists).into()
+    }
+
+    fn part2(lists: &Self::Input) -> Answer {
+        second_task(lists).into()
+    }
+}
+
+aoc_common::example_tests!(Day1: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lenient_parse_skips_unreadable_lines() {
+        let lists = Day1::parse("3   4\nfour   3\n2   5\n7 8 9\n").unwrap();
+        assert_eq!(lists.columns, vec![vec![3, 2], vec![4, 5]]);
+    }
+
+    #[test]
+    fn parse_takes_any_whitespace_columns_and_wide_ids() {
+        let lists = Day1::parse("3\t4 9\n  -4  3\t 9876543210\n").unwrap();
+        assert_eq!(lists.columns, vec![vec![3, -4], vec![4, 3], vec![9, 9_876_543_210]]);
+        assert_eq!(lists.pair(), (&[3, -4][..], &[4, 3][..]));
+        assert_eq!(Day1::parse("").unwrap().pair(), (&[][..], &[][..]));
+    }
+
+    #[test]
+    fn strict_parse_reports_unreadable_lines() {
+        let params = Params::new().strict(true);
+        let error = Day1::parse_with("3   4\n4   x3\n", &params).unwrap_err();
+        assert_eq!(error.to_string(), "2:5: expected location ID, found `x3`");
+
+        let error = Day1::parse_with("3   4\n3   4   5\n", &params).unwrap_err();
+        assert_eq!(error.expected_token(), Some("2 location IDs"));
+    }
+
+    #[test]
+    fn similarity_counts_every_repeat_on_both_sides() {
+        let lists = Day1::parse("3 3\n3 3\n5 3\n1 5\n").unwrap();
+        assert_eq!(second_task(&lists), 3 * 3 + 3 * 3 + 5);
+    }
+
+    #[test]
+    fn extreme_ids_do_not_overflow() {
+        let lists = Day1::parse(&format!("{} {}\n{} {}\n", i64::MIN, i64::MAX, i64::MAX, i64::MAX)).unwrap();
+        assert_eq!(first_task(&lists), i128::from(u64::MAX));
+        assert_eq!(second_task(&lists), 2 * i128::from(i64::MAX));
+    }
+}
@@ -0,0 +1,262 @@
+//! A closer look at the lists: what each column holds, how far apart the
+//! paired IDs are, and which IDs make up most of both answers.
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use crate::{frequencies, sorted, Lists};
+
+/// Widest histogram bar, in characters.
+const BAR: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub count: usize,
+    pub distinct: usize,
+    pub min: i64,
+    pub max: i64,
+    pub sum: i128,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl ColumnStats {
+    /// None for an empty column.
+    fn of(column: &[i64]) -> Option<ColumnStats> {
+        let sorted = sorted(column);
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (i128::from(sorted[middle - 1]) + i128::from(sorted[middle])) as f64 / 2.0
+        } else {
+            sorted[middle] as f64
+        };
+        let sum: i128 = sorted.iter().map(|&id| i128::from(id)).sum();
+        Some(ColumnStats {
+            count: sorted.len(),
+            distinct: frequencies(&sorted).len(),
+            min,
+            max,
+            sum,
+            mean: sum as f64 / sorted.len() as f64,
+            median,
+        })
+    }
+}
+
+/// Pairs whose distance lies in `low..=high`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub low: u64,
+    pub high: u64,
+    pub count: usize,
+}
+
+/// A pair of the sorted lists and its share of the total distance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distance {
+    pub left: i64,
+    pub right: i64,
+    pub distance: u64,
+}
+
+/// An ID of the left list and its share of the similarity score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Similarity {
+    pub id: i64,
+    pub left_count: i64,
+    pub right_count: i64,
+    pub score: i128,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub columns: Vec<Option<ColumnStats>>,
+    /// Distances of the sorted pairs in power-of-two buckets: 0, 1, 2–3, 4–7…
+    pub histogram: Vec<Bucket>,
+    pub distances: Vec<Distance>,
+    pub similarities: Vec<Similarity>,
+}
+
+fn bucket(distance: u64) -> usize {
+    (u64::BITS - distance.leading_zeros()) as usize
+}
+
+fn bucket_bounds(index: usize) -> (u64, u64) {
+    match index {
+        0 => (0, 0),
+        _ => (1 << (index - 1), u64::MAX >> (u64::BITS as usize - index)),
+    }
+}
+
+/// Describes the lists, keeping the `top` biggest contributors to each
+/// answer.
+pub fn report(lists: &Lists, top: usize) -> Report {
+    let (left, right) = lists.pair();
+    let (left, right) = (sorted(left), sorted(right));
+
+    let mut distances: Vec<Distance> = left
+        .iter()
+        .zip(right.iter())
+        .map(|(&left, &right)| Distance {
+            left,
+            right,
+            distance: left.abs_diff(right),
+        })
+        .collect();
+
+    let mut histogram = Vec::new();
+    for distance in distances.iter() {
+        let index = bucket(distance.distance);
+        if histogram.len() <= index {
+            histogram.resize_with(index + 1, || 0);
+        }
+        histogram[index] += 1;
+    }
+    let histogram = histogram
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| {
+            let (low, high) = bucket_bounds(index);
+            Bucket { low, high, count }
+        })
+        .collect();
+
+    distances.sort_by_key(|distance| (Reverse(distance.distance), distance.left));
+    distances.truncate(top);
+
+    let right_counts = frequencies(&right);
+    let mut similarities: Vec<Similarity> = frequencies(&left)
+        .into_iter()
+        .filter_map(|(id, left_count)| {
+            let right_count = *right_counts.get(&id)?;
+            Some(Similarity {
+                id,
+                left_count,
+                right_count,
+                score: i128::from(id) * i128::from(left_count) * i128::from(right_count),
+            })
+        })
+        .collect();
+    similarities.sort_by_key(|similarity| (Reverse(similarity.score), similarity.id));
+    similarities.truncate(top);
+
+    Report {
+        columns: lists.columns.iter().map(|column| ColumnStats::of(column)).collect(),
+        histogram,
+        distances,
+        similarities,
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, stats) in self.columns.iter().enumerate() {
+            write!(f, "column {}: ", index + 1)?;
+            match stats {
+                Some(stats) => writeln!(
+                    f,
+                    "{} IDs, {} distinct, min {}, max {}, sum {}, mean {:.2}, median {}",
+                    stats.count, stats.distinct, stats.min, stats.max, stats.sum, stats.mean, stats.median
+                )?,
+                None => writeln!(f, "empty")?,
+            }
+        }
+
+        writeln!(f, "\ndistances:")?;
+        let most = self.histogram.iter().map(|bucket| bucket.count).max().unwrap_or(0);
+        for bucket in self.histogram.iter() {
+            let range = if bucket.low == bucket.high {
+                bucket.low.to_string()
+            } else {
+                format!("{}-{}", bucket.low, bucket.high)
+            };
+            let bar = (bucket.count * BAR).div_ceil(most.max(1));
+            writeln!(f, "{:>13} {:>7}  {}", range, bucket.count, "#".repeat(bar))?;
+        }
+
+        writeln!(f, "\nlargest distances:")?;
+        for distance in self.distances.iter() {
+            writeln!(f, "{:>13} {:>13} {:>10}", distance.left, distance.right, distance.distance)?;
+        }
+
+        writeln!(f, "\nlargest similarity scores:")?;
+        for similarity in self.similarities.iter() {
+            writeln!(
+                f,
+                "{:>13} {:>4} x left {:>4} x right {:>12}",
+                similarity.id, similarity.left_count, similarity.right_count, similarity.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day1;
+    use aoc_common::Solution;
+
+    fn example() -> Lists {
+        Day1::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap()
+    }
+
+    #[test]
+    fn column_stats_of_the_example() {
+        let report = report(&example(), 3);
+        let left = report.columns[0].clone().unwrap();
+        assert_eq!((left.count, left.distinct, left.min, left.max, left.sum), (6, 4, 1, 4, 16));
+        assert_eq!(left.median, 3.0);
+        let right = report.columns[1].clone().unwrap();
+        assert_eq!((right.min, right.max, right.median), (3, 9, 3.5));
+    }
+
+    #[test]
+    fn histogram_and_contributors_add_up_to_the_answers() {
+        let report = report(&example(), 2);
+        let counts: Vec<(u64, u64, usize)> = report
+            .histogram
+            .iter()
+            .map(|bucket| (bucket.low, bucket.high, bucket.count))
+            .collect();
+        // distances 2, 1, 0, 1, 2, 5
+        assert_eq!(counts, vec![(0, 0, 1), (1, 1, 2), (2, 3, 2), (4, 7, 1)]);
+
+        let distances: Vec<u64> = report.distances.iter().map(|distance| distance.distance).collect();
+        assert_eq!(distances, vec![5, 2]);
+        assert_eq!(
+            report.similarities[0],
+            Similarity {
+                id: 3,
+                left_count: 3,
+                right_count: 3,
+                score: 27
+            }
+        );
+        assert_eq!(report.similarities[1].score, 4);
+    }
+
+    #[test]
+    fn report_prints_every_section() {
+        let printed = report(&example(), 1).to_string();
+        assert!(printed.starts_with("column 1: 6 IDs, 4 distinct, min 1, max 4, sum 16, mean 2.67, median 3\n"));
+        assert!(printed.contains("\n          4-7       1  ####################\n"), "{}", printed);
+        assert!(printed.contains("largest similarity scores:\n            3    3 x left    3 x right           27\n"));
+    }
+
+    #[test]
+    fn ids_at_the_ends_of_i64_fit() {
+        let lists = Day1::parse(&format!("{} {}\n{} {}\n", i64::MIN, i64::MAX, i64::MAX, i64::MAX)).unwrap();
+        let report = report(&lists, 1);
+        let left = report.columns[0].clone().unwrap();
+        assert_eq!((left.sum, left.median), (-1, -0.5));
+        let right = report.columns[1].clone().unwrap();
+        assert_eq!(right.sum, 2 * i128::from(i64::MAX));
+
+        let last = report.histogram.last().unwrap();
+        assert_eq!((last.low, last.high, last.count), (1 << 63, u64::MAX, 1));
+        assert_eq!(report.distances[0].distance, u64::MAX);
+        assert_eq!(report.similarities[0].score, i128::from(i64::MAX) * 2);
+    }
+}
@@ -0,0 +1,127 @@
+use aoc_common::pathfinding::flood_fill;
+use aoc_common::point::ORTHOGONAL;
+use aoc_common::{Answer, Grid, ParseError, Point, Solution};
+use std::collections::{HashMap, HashSet};
+
+pub type Matrix = Grid<char>;
+
+/// Every plot of the region around `start_position`, with its fence count.
+fn find_cluster(matrix: &Matrix, start_position: Point<usize>) -> HashMap<Point<usize>, usize> {
+    let plant = matrix[start_position];
+    let same_plant = |position: &Point<usize>| {
+        matrix
+            .neighbours(*position, &ORTHOGONAL)
+            .filter(|&next| matrix[next] == plant)
+            .collect::<Vec<_>>()
+    };
+
+    flood_fill(start_position, same_plant)
+        .into_keys()
+        .map(|position| {
+            let fances = 4 - same_plant(&position).len();
+            (position, fances)
+        })
+        .collect()
+}
+
+fn first_task(matrix: &Matrix) -> usize {
+    let mut count = 0;
+    let mut visited: HashSet<Point<usize>> = HashSet::new();
+
+    for ((x, y), _) in matrix.cells() {
+        let current_position = Point::new(x, y);
+        if visited.contains(&current_position) {
+            continue;
+        }
+        let cluster = find_cluster(matrix, current_position);
+        count += cluster.len() * cluster.values().sum::<usize>();
+        let cluster_keys: Vec<_> = cluster.keys().copied().collect();
+        visited.extend(cluster_keys);
+    }
+    count
+}
+
+fn second_task(matrix: &Matrix) -> usize {
+    first_task(matrix)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Matrix;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::from_chars(input)
+    }
+
+    fn part1(matrix: &Self::Input) -> Answer {
+        first_task(matrix).into()
+    }
+
+    fn part2(matrix: &Self::Input) -> Answer {
+        second_task(matrix).into()
+    }
+}
+
+aoc_common::example_tests!(Day12: example1, example2, example3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_task_with_first_sample() {
+        let first_sample_map = Matrix::from_rows(vec![
+            vec!['A', 'A', 'A', 'A'],
+            vec!['B', 'B', 'C', 'D'],
+            vec!['B', 'B', 'C', 'C'],
+            vec!['E', 'E', 'E', 'C'],
+        ])
+        .unwrap();
+
+        let result = first_task(&first_sample_map);
+        assert_eq!(result, 140);
+    }
+
+    #[test]
+    fn first_task_with_second_sample() {
+        let second_sample_map = Matrix::from_rows(vec![
+            vec!['O', 'O', 'O', 'O', 'O'],
+            vec!['O', 'X', 'O', 'X', 'O'],
+            vec!['O', 'O', 'O', 'O', 'O'],
+            vec!['O', 'X', 'O', 'X', 'O'],
+            vec!['O', 'O', 'O', 'O', 'O'],
+        ])
+        .unwrap();
+
+        let result = first_task(&second_sample_map);
+        assert_eq!(result, 772);
+    }
+
+    #[test]
+    fn parse_builds_grid_from_lines() {
+        let matrix = Day12::parse("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        assert_eq!((matrix.width(), matrix.height()), (4, 4));
+        assert_eq!(Day12::part1(&matrix), Answer::Number(140));
+    }
+
+    #[test]
+    fn first_task_with_third_sample() {
+        let third_sample_map = Matrix::from_rows(vec![
+            vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'F', 'F'],
+            vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'C', 'F'],
+            vec!['V', 'V', 'R', 'R', 'R', 'C', 'C', 'F', 'F', 'F'],
+            vec!['V', 'V', 'R', 'C', 'C', 'C', 'J', 'F', 'F', 'F'],
+            vec!['V', 'V', 'V', 'V', 'C', 'J', 'J', 'C', 'F', 'E'],
+            vec!['V', 'V', 'I', 'V', 'C', 'C', 'J', 'J', 'E', 'E'],
+            vec!['V', 'V', 'I', 'I', 'I', 'C', 'J', 'J', 'E', 'E'],
+            vec!['M', 'I', 'I', 'I', 'I', 'I', 'J', 'J', 'E', 'E'],
+            vec!['M', 'I', 'I', 'I', 'S', 'I', 'J', 'E', 'E', 'E'],
+            vec!['M', 'M', 'M', 'I', 'S', 'S', 'J', 'E', 'E', 'E'],
+        ])
+        .unwrap();
+
+        let result = first_task(&third_sample_map);
+        assert_eq!(result, 1930);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,291 @@
+use aoc_common::cancel;
+use aoc_common::parse::{numbered_lines, parse_token};
+use aoc_common::{Answer, Params, ParseError, Point, Solution};
+use tracing::debug;
+
+#[derive(Debug)]
+pub struct Machine {
+    button_a: Point<i64>,
+    button_b: Point<i64>,
+    prize: Point<i64>
+}
+
+fn extract_vector(line_number: usize, line: &str, re: &regex::Regex, expected: &str) -> Result<Point<i64>, ParseError> {
+    let caps = re
+        .captures(line)
+        .ok_or_else(|| ParseError::expected(line_number, 1, expected, line))?;
+    Ok(Point {
+        x: parse_token(line_number, line, caps.get(1).unwrap().as_str(), "X coordinate")?,
+        y: parse_token(line_number, line, caps.get(2).unwrap().as_str(), "Y coordinate")?
+    })
+}
+
+fn next_line<'a, I>(lines: &mut I, previous: usize, expected: &str) -> Result<(usize, &'a str), ParseError>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    lines
+        .next()
+        .ok_or_else(|| ParseError::expected(previous + 1, 1, expected, ""))
+}
+
+fn extract_machines(input: &str, strict: bool) -> Result<Vec<Machine>, ParseError> {
+    let mut lines = numbered_lines(input);
+    let mut aoa = Vec::new();
+    let button_a_re = regex::Regex::new(r"Button A: X\+(\d+), Y\+(\d+)").unwrap();
+    let button_b_re = regex::Regex::new(r"Button B: X\+(\d+), Y\+(\d+)").unwrap();
+    let prize_re = regex::Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
+
+    while let Some((number_a, line_a)) = lines.next() {
+        if line_a.trim().is_empty() {
+            continue;
+        }
+        let (number_b, line_b) = next_line(&mut lines, number_a, "Button B line")?;
+        let (number_p, line_p) = next_line(&mut lines, number_b, "Prize line")?;
+        if let Some((number, separator)) = lines.next() {
+            if strict && !separator.trim().is_empty() {
+                return Err(ParseError::expected(number, 1, "blank line between machines", separator));
+            }
+        }
+        aoa.push(Machine {
+            button_a: extract_vector(number_a, line_a, &button_a_re, "Button A: X+<x>, Y+<y>")?,
+            button_b: extract_vector(number_b, line_b, &button_b_re, "Button B: X+<x>, Y+<y>")?,
+            prize: extract_vector(number_p, line_p, &prize_re, "Prize: X=<x>, Y=<y>")?,
+        });
+    }
+    Ok(aoa)
+}
+
+const A_COST: usize = 3;
+const B_COST: usize = 1;
+
+fn find_lowest_cost(a_pressed: usize, b_pressed: usize, costs: usize) -> usize {
+    let current_cost = a_pressed * A_COST + b_pressed * B_COST;
+    if costs > current_cost || costs == 0 {
+        current_cost
+    } else {
+        costs
+    }
+}
+
+const PRESS_LIMIT: usize = 100;
+
+fn find_lowest_cost_with_limit(a_pressed: usize, b_pressed: usize, costs: usize) -> usize {
+    if a_pressed <= PRESS_LIMIT && b_pressed <= PRESS_LIMIT {
+        find_lowest_cost(a_pressed, b_pressed, costs)
+    } else {
+        costs
+    }
+}
+
+fn find_chepest_prize_with_limit(machine: &Machine) -> usize {
+    let mut a_pressed: usize = 0;
+
+    let mut x = machine.prize.x;
+    let mut y = machine.prize.y;
+    let mut costs : usize = 0;
+
+    while y > 0 && x > 0 {
+        if x % machine.button_b.x == 0
+            && y % machine.button_b.y == 0
+            && x / machine.button_b.x == y / machine.button_b.y
+        {
+            let b_pressed = (x / machine.button_b.x) as usize;
+            costs = find_lowest_cost_with_limit(a_pressed, b_pressed, costs);
+        }
+        if a_pressed.is_multiple_of(4096) && cancel::cancelled() {
+            break;
+        }
+        x -= machine.button_a.x;
+        y -= machine.button_a.y;
+        a_pressed += 1;
+    }
+
+    costs
+}
+
+fn first_task(machines: &[Machine]) -> usize {
+    let mut count = 0;
+
+    for machine in machines.iter() {
+        let min_tokens = find_chepest_prize_with_limit(machine);
+        debug!(min_tokens, ?machine, "cheapest prize");
+        count += min_tokens
+    }
+    count
+}
+
+const PRECISION_ERROR: i64 = 10000000000000;
+
+/// Solves `a * button_a + b * button_b = prize` with Cramer's rule. Buttons
+/// that push the same way have no single answer, so those are walked.
+fn find_chepest_prize(machine: &Machine) -> usize {
+    let [a, b, p] = [machine.button_a, machine.button_b, machine.prize].map(|point| Point {
+        x: i128::from(point.x),
+        y: i128::from(point.y),
+    });
+    let determinant = a.x * b.y - a.y * b.x;
+    if determinant == 0 {
+        return walk_chepest_prize(machine);
+    }
+    let a_numerator = p.x * b.y - p.y * b.x;
+    let b_numerator = a.x * p.y - a.y * p.x;
+    if a_numerator % determinant != 0 || b_numerator % determinant != 0 {
+        return 0;
+    }
+    match (usize::try_from(a_numerator / determinant), usize::try_from(b_numerator / determinant)) {
+        (Ok(a_pressed), Ok(b_pressed)) => find_lowest_cost_with_limit(a_pressed, b_pressed, 0),
+        _ => 0,
+    }
+}
+
+/// Tries every number of A presses, for buttons that push the same way.
+fn walk_chepest_prize(machine: &Machine) -> usize {
+    let mut a_pressed: usize = 0;
+
+    let mut x = machine.prize.x;
+    let mut y = machine.prize.y;
+    let mut costs : usize = 0;
+
+    while y > 0 && x > 0 {
+        if x % machine.button_b.x == 0
+            && y % machine.button_b.y == 0
+            && x / machine.button_b.x == y / machine.button_b.y
+        {
+            let b_pressed = (x / machine.button_b.x) as usize;
+            costs = find_lowest_cost_with_limit(a_pressed, b_pressed, costs);
+        }
+        if a_pressed.is_multiple_of(4096) && cancel::cancelled() {
+            break;
+        }
+        x -= machine.button_a.x;
+        y -= machine.button_a.y;
+        a_pressed += 1;
+    }
+    costs
+}
+
+fn second_task(machines: &[Machine]) -> usize {
+    let fixed_machines: Vec<Machine> = machines.iter().map(|machine| Machine {
+        button_a: machine.button_a,
+        button_b: machine.button_b,
+        prize: machine.prize + Point::new(PRECISION_ERROR, PRECISION_ERROR)
+    }).collect();
+    let mut count = 0;
+
+    for machine in fixed_machines.iter() {
+        if cancel::cancelled() {
+            break;
+        }
+        let min_tokens = find_chepest_prize(machine);
+        debug!(min_tokens, ?machine, "cheapest prize");
+        count += min_tokens
+    }
+    count
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        extract_machines(input, false)
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        extract_machines(input, params.is_strict())
+    }
+
+    fn part1(machines: &Self::Input) -> Answer {
+        first_task(machines).into()
+    }
+
+    fn part2(machines: &Self::Input) -> Answer {
+        second_task(machines).into()
+    }
+}
+
+aoc_common::example_tests!(Day13: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_machines_from_str(input: &str) -> Vec<Machine> {
+        extract_machines(input, false).unwrap()
+    }
+    
+    const MACHINES: &str = 
+     r#"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+"#;
+
+    #[test]
+    fn first_task_with_first_sample() {
+        let machines: Vec<Machine> = read_machines_from_str(MACHINES);
+
+        let result = first_task(&machines);
+        assert_eq!(result, 480);
+    }
+
+    #[test]
+    fn second_task_with_first_sample() {
+        let machines: Vec<Machine> = read_machines_from_str(MACHINES);
+
+        let result = second_task(&machines);
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn cheapest_prize_solves_the_presses() {
+        let machines: Vec<Machine> = read_machines_from_str(MACHINES);
+
+        let costs: Vec<usize> = machines.iter().map(find_chepest_prize).collect();
+        assert_eq!(costs, vec![280, 0, 200, 0]);
+    }
+
+    #[test]
+    fn cheapest_prize_walks_parallel_buttons() {
+        let machine = read_machines_from_str("Button A: X+2, Y+4\nButton B: X+1, Y+2\nPrize: X=10, Y=20\n");
+
+        assert_eq!(find_chepest_prize(&machine[0]), 10);
+    }
+
+    #[test]
+    fn parse_reports_malformed_button() {
+        let input = "Button A: X+94, Y+34\nButton B: X-22, Y+67\nPrize: X=8400, Y=5400\n";
+        let error = Day13::parse(input).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.expected_token(), Some("Button B: X+<x>, Y+<y>"));
+    }
+
+    #[test]
+    fn parse_reports_missing_prize() {
+        let error = Day13::parse("Button A: X+94, Y+34\nButton B: X+22, Y+67\n").unwrap_err();
+        assert_eq!(error.to_string(), "3:1: expected Prize line, found nothing");
+    }
+
+    #[test]
+    fn strict_parse_requires_blank_separator() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\nstray\n";
+        assert_eq!(Day13::parse(input).unwrap().len(), 1);
+
+        let error = Day13::parse_with(input, &Params::new().strict(true)).unwrap_err();
+        assert_eq!(error.to_string(), "4:1: expected blank line between machines, found `stray`");
+    }
+
+}
\ No newline at end of file
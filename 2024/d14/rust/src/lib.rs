@@ -0,0 +1,184 @@
+use aoc_common::parse::{numbered_lines, parse_token};
+use aoc_common::{Answer, Params, ParseError, Point, Solution};
+use tracing::{debug, trace};
+
+pub const SPACE_WIDE: usize = 101;
+pub const SPACE_TALL: usize = 103;
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    position: Point<i32>,
+    velocity: Point<i32>
+}
+
+impl Robot {
+    fn move_to(&self, wide: i32, tall: i32) -> Robot {
+        let position = self.position + self.velocity;
+        let wrap = |coordinate: i32, size: i32| {
+            if coordinate >= size {
+                coordinate - size
+            } else if coordinate < 0 {
+                size + coordinate
+            } else {
+                coordinate
+            }
+        };
+        Robot {
+            position: Point::new(wrap(position.x, wide), wrap(position.y, tall)),
+            velocity: self.velocity
+        }
+    }
+        
+}
+fn extract_robot(line_number: usize, line: &str, re: &regex::Regex) -> Result<Robot, ParseError> {
+    let caps = re
+        .captures(line)
+        .ok_or_else(|| ParseError::expected(line_number, 1, "p=<x>,<y> v=<dx>,<dy>", line))?;
+    let value = |index: usize, expected: &str| {
+        parse_token(line_number, line, caps.get(index).unwrap().as_str(), expected)
+    };
+    Ok(Robot {
+        position: Point {
+            x: value(1, "X position")?,
+            y: value(2, "Y position")?
+        },
+        velocity: Point {
+            x: value(3, "X velocity")?,
+            y: value(4, "Y velocity")?
+        }
+    })
+}
+
+fn extract_robots(input: &str) -> Result<Vec<Robot>, ParseError> {
+    let mut aoa = Vec::new();
+    let robot_re = regex::Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
+
+    for (line_number, line) in numbered_lines(input) {
+        if !line.trim().is_empty() {
+            aoa.push(extract_robot(line_number, line, &robot_re)?);
+        }
+    }
+    Ok(aoa)
+}
+
+/// Robots together with the size of the space they patrol.
+#[derive(Debug, Clone)]
+pub struct Space {
+    pub robots: Vec<Robot>,
+    pub wide: usize,
+    pub tall: usize,
+}
+
+fn first_task(machines: &[Robot], wide: usize, tall: usize) -> i32 {
+    const MAX_ITERATIONS: usize = 100;
+    let mut robots: Vec<Robot> = machines.to_vec();
+
+    for _ in 0..MAX_ITERATIONS {
+        robots = robots.into_iter().map(|robot| {
+            robot.move_to(wide as i32, tall as i32)
+        }).collect();
+    }
+
+    let middle_wide = (wide / 2) as i32;
+    let middle_tall = (tall / 2) as i32;
+    debug!(middle_wide, middle_tall, "middle lines");
+    let mut top_left: i32 = 0;
+    let mut top_right:i32 = 0;
+    let mut bottom_left:i32 = 0;
+    let mut bottom_right:i32 = 0;
+    robots.iter().for_each(|robot| {
+        trace!(?robot);
+        if robot.position.x < middle_wide && robot.position.y < middle_tall {
+            top_left += 1;
+        } else if robot.position.x > middle_wide && robot.position.y < middle_tall {
+            top_right += 1;
+        } else if robot.position.x < middle_wide && robot.position.y > middle_tall {
+            bottom_left += 1;
+        } else if robot.position.x > middle_wide && robot.position.y > middle_tall {
+            bottom_right += 1;
+        }
+    });
+    debug!(top_left, top_right, bottom_left, bottom_right, "robots per quadrant");
+    top_left * top_right * bottom_left * bottom_right
+}
+
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Space;
+
+    const PARAMS: &'static [&'static str] = &["wide", "tall"];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok(Space {
+            robots: extract_robots(input)?,
+            wide: params.get("wide")?.unwrap_or(SPACE_WIDE),
+            tall: params.get("tall")?.unwrap_or(SPACE_TALL),
+        })
+    }
+
+    fn part1(space: &Self::Input) -> Answer {
+        first_task(&space.robots, space.wide, space.tall).into()
+    }
+}
+
+aoc_common::example_tests!(Day14: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_robots_from_str(input: &str) -> Vec<Robot> {
+        extract_robots(input).unwrap()
+    }
+    
+    const MACHINES: &str = 
+r#"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+"#;
+
+    #[test]
+    fn first_task_with_first_sample() {
+        let machines: Vec<Robot> = read_robots_from_str(MACHINES);
+
+        let result = first_task(&machines, 11, 7);
+        assert_eq!(result, 12);
+    }
+
+    #[test]
+    fn parse_with_overrides_space_size() {
+        let params = Params::new().with("wide", 11).with("tall", 7);
+        let space = Day14::parse_with(MACHINES, &params).unwrap();
+
+        assert_eq!((space.wide, space.tall), (11, 7));
+        assert_eq!(Day14::part1(&space), Answer::Number(12));
+    }
+
+    #[test]
+    fn parse_reports_malformed_robot() {
+        let error = Day14::parse("p=0,4 v=3,-3\np=6;3 v=-1,-3\n").unwrap_err();
+        assert_eq!(error.to_string(), "2:1: expected p=<x>,<y> v=<dx>,<dy>, found `p=6;3 v=-1,-3`");
+    }
+
+    #[test]
+    fn parse_reports_out_of_range_velocity() {
+        let error = Day14::parse("p=0,4 v=3,-99999999999\n").unwrap_err();
+        assert_eq!(error.to_string(), "1:11: expected Y velocity, found `-99999999999`");
+    }
+
+}
\ No newline at end of file
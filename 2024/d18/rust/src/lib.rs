@@ -0,0 +1,276 @@
+use aoc_common::cancel;
+use aoc_common::parse::{numbered_lines, parse_token};
+use aoc_common::pathfinding::grid_path;
+use aoc_common::{Answer, Grid, Params, ParseError, Point, Solution};
+use std::cmp::max;
+use tracing::{debug, trace, Level};
+
+pub const ITTERATIONS: usize = 1024;
+pub const DIMENSION: usize = 71;
+
+pub type Memory = Point<usize>;
+
+fn extract_memory(input: &str, strict: bool) -> Result<Vec<Memory>, ParseError> {
+    let mut falling_bytes = Vec::new();
+    for (line_number, line) in numbered_lines(input) {
+        if line.trim().is_empty() {
+            break;
+        }
+        let parts = line.trim().split(',').collect::<Vec<&str>>();
+        if strict {
+            if let [x, y] = parts[..] {
+                let x = parse_token(line_number, line, x, "X coordinate")?;
+                let y = parse_token(line_number, line, y, "Y coordinate")?;
+                falling_bytes.push(Memory { x, y });
+            } else {
+                return Err(ParseError::expected(line_number, 1, "a coordinate X,Y", line));
+            }
+        } else if parts.len() == 2 {
+            if let (Ok(x), Ok(y)) = (parts[0].parse::<usize>(), parts[1].parse::<usize>()) {
+                falling_bytes.push(Memory { x, y });
+            }
+        }
+    }
+    Ok(falling_bytes)
+}
+
+/// Bytes in the order they fall, with the simulation bounds.
+#[derive(Debug, Clone)]
+pub struct FallingMemory {
+    pub bytes: Vec<Memory>,
+    pub itterations: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+fn trace_memory(memory: &Grid<bool>) {
+    if tracing::enabled!(Level::TRACE) {
+        trace!("memory\n{}", memory.render(|&cell| if cell { '#' } else { '.' }));
+    }
+}
+
+/// Steps from `from` to `to` around the corrupted bytes, if the exit is reachable.
+fn find_shortest_path(memory: &Grid<bool>, from: Memory, to: Memory) -> Option<usize> {
+    grid_path(memory, from, to, |&corrupted| !corrupted).map(|path| path.steps())
+}
+
+fn first_task(falling_memory: Vec<Memory>, itterations: usize, width: usize, height: usize) -> Option<usize> {
+    let mut memory = Grid::new(width, height, false);
+
+    let mut counter = 0;
+    for byte in falling_memory.iter() {
+        memory.set(byte.x, byte.y, true);
+        counter += 1;
+        if counter >= itterations {
+            break;
+        }
+    }
+    trace_memory(&memory);
+
+    find_shortest_path(&memory, Memory { x: 0, y: 0 }, Memory { x: width-1, y: height-1 })
+}
+
+fn construct_memory_graph(falling_memory: Vec<Memory>, itterations: usize, counter: usize, map: Grid<bool>) -> Grid<bool> {
+    let mut memory = map.clone();
+    let mut count = counter;
+    for byte in falling_memory.iter() {
+        memory.set(byte.x, byte.y, true);
+        count += 1;
+        if count >= itterations {
+            break;
+        }
+    }
+    memory
+}
+/// The first byte that cuts off the exit, or None when none does or the
+/// search was cancelled.
+fn second_task(falling_memory: Vec<Memory>, itter: usize, width: usize, height: usize) -> Option<Memory> {
+    let size = falling_memory.len();
+    let map = Grid::new(width, height, false);
+    let mut itterations = itter + (size - itter)/2;
+    let mut counter = 0;
+    let mut last_reachable = false;
+    let from = Memory { x: 0, y: 0 };
+    let to = Memory { x: width-1, y: height-1 };
+    loop {
+        if cancel::cancelled() {
+            return None;
+        }
+        let next_map= construct_memory_graph(falling_memory.clone(), itterations, 0, map.clone());
+        let path_size = find_shortest_path(&next_map, from, to);
+        debug!(counter, itterations, ?path_size, "bisect");
+        let reachable = path_size.is_some();
+        if reachable && !last_reachable && counter == itterations {
+            trace_memory(&next_map);
+            break;
+        } else if !reachable {
+            itterations = itterations - max((itterations - counter)/2,1);
+            last_reachable = reachable;
+        } else if itterations >= size {
+            // every byte has fallen and the exit is still reachable
+            return None;
+        } else {
+            counter = itterations;
+            itterations = itterations + max((size - itterations)/2,1);
+            last_reachable = reachable;
+        }
+    }
+    falling_memory.get(itterations).copied()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = FallingMemory;
+
+    const PARAMS: &'static [&'static str] = &["iterations", "dimension", "width", "height"];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        // `dimension` sizes a square space, `width` and `height` override one side
+        let dimension = params.get("dimension")?.unwrap_or(DIMENSION);
+        let bytes = extract_memory(input, params.is_strict())?;
+        let itterations = params.get("iterations")?.unwrap_or(ITTERATIONS);
+        if bytes.len() < itterations {
+            return Err(ParseError::new(format!(
+                "only {} bytes fall, fewer than the {} iterations",
+                bytes.len(),
+                itterations
+            )));
+        }
+        Ok(FallingMemory {
+            bytes,
+            itterations,
+            width: params.get("width")?.unwrap_or(dimension),
+            height: params.get("height")?.unwrap_or(dimension),
+        })
+    }
+
+    fn part1(memory: &Self::Input) -> Answer {
+        first_task(memory.bytes.clone(), memory.itterations, memory.width, memory.height)
+            .map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part2(memory: &Self::Input) -> Answer {
+        second_task(memory.bytes.clone(), memory.itterations, memory.width, memory.height)
+            .map_or(Answer::Unsolved, |byte| byte.to_string().into())
+    }
+}
+
+aoc_common::example_tests!(Day18: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_memory_from_str(input: &str) -> Vec<Memory> {
+        extract_memory(input, false).unwrap()
+    }
+    
+    const MACHINES: &str = 
+r#"5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+"#;
+
+    #[test]
+    fn first_task_with_first_sample() {
+        let falling_bytes: Vec<Memory> = read_memory_from_str(MACHINES);
+
+        let result = first_task(falling_bytes, 12, 7, 7);
+        assert_eq!(result, Some(22));
+    }
+
+    #[test]
+    fn second_task_with_first_sample() {
+        let falling_bytes: Vec<Memory> = read_memory_from_str(MACHINES);
+
+        let result = second_task(falling_bytes, 12, 7, 7);
+        assert_eq!(result.map(|byte| byte.to_string()).as_deref(), Some("6,1"));
+    }
+
+    #[test]
+    fn second_task_without_a_blocking_byte() {
+        let falling_bytes = read_memory_from_str("1,0\n1,1\n");
+
+        assert_eq!(second_task(falling_bytes, 1, 3, 3), None);
+    }
+
+    #[test]
+    fn second_task_gives_up_when_cancelled() {
+        let token = cancel::CancelToken::new();
+        token.cancel();
+        let result = cancel::with_token(&token, || second_task(read_memory_from_str(MACHINES), 12, 7, 7));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn parse_rejects_fewer_bytes_than_iterations() {
+        let error = Day18::parse_with("5,4\n4,2\n", &Params::new().with("iterations", 3)).unwrap_err();
+        assert_eq!(error.to_string(), "only 2 bytes fall, fewer than the 3 iterations");
+    }
+
+    #[test]
+    fn parse_with_overrides_simulation_bounds() {
+        let params = Params::new().with("iterations", 12).with("dimension", 7);
+        let memory = Day18::parse_with(MACHINES, &params).unwrap();
+
+        assert_eq!(Day18::part1(&memory), Answer::Number(22));
+        assert_eq!(Day18::part2(&memory), Answer::from("6,1"));
+    }
+
+    #[test]
+    fn first_task_with_non_square_space() {
+        // a wall across the middle row leaves a single gap on the far right
+        let falling_bytes = read_memory_from_str("0,1\n1,1\n2,1\n3,1\n");
+
+        assert_eq!(first_task(falling_bytes.clone(), 4, 5, 3), Some(6));
+        assert_eq!(first_task(falling_bytes, 4, 4, 3), None);
+    }
+
+    #[test]
+    fn parse_with_width_and_height() {
+        let params = Params::new().with("dimension", 7).with("width", 9).with("iterations", 12);
+        let memory = Day18::parse_with(MACHINES, &params).unwrap();
+
+        assert_eq!((memory.width, memory.height), (9, 7));
+    }
+
+    #[test]
+    fn strict_parse_reports_skipped_bytes() {
+        let input = "5,4\n4;2\n4,5\n";
+        assert_eq!(read_memory_from_str(input).len(), 2);
+
+        let error = Day18::parse_with(input, &Params::new().strict(true)).unwrap_err();
+        assert_eq!(error.to_string(), "2:1: expected a coordinate X,Y, found `4;2`");
+
+        let error = Day18::parse_with("5,-4\n", &Params::new().strict(true)).unwrap_err();
+        assert_eq!(error.to_string(), "1:3: expected Y coordinate, found `-4`");
+    }
+
+}
@@ -0,0 +1,198 @@
+use aoc_common::cancel;
+use aoc_common::{Answer, ParseError, Solution};
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use tracing::{debug_span, trace};
+
+fn extract(input: &str) -> (Vec<String>, Vec<String>) {
+    let mut lines = input.lines();
+    let mut patterns = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        patterns = line.trim().split(", ").map(|str| str.trim().to_string()).collect::<Vec<String>>();
+    
+    }
+
+    let mut designs = Vec::new();
+    for line in lines {
+        if line.trim().is_empty() {
+            break;
+        }
+        let design = line.trim().to_string();
+        designs.push(design.clone());
+    }
+    (patterns, designs)
+}
+
+fn find_pattern(dsgn: &str, patterns: &[String]) -> bool {
+    let mut queue: VecDeque<String> = VecDeque::new();
+    queue.push_back(dsgn.to_string());
+    let mut found = false;
+    while let Some(design) = queue.pop_back()  {
+        if cancel::cancelled() {
+            break;
+        }
+        for pattern in patterns.iter() {
+            if design.starts_with(pattern) {
+                let mut d = design.clone();
+                d.replace_range(0..pattern.len(), "");
+                queue.push_back(d);
+            }
+        }
+        if design.is_empty() {
+            found = true;
+            break;
+        }
+    }
+    trace!(design = dsgn, found);
+    found
+}
+
+fn find_all_patterns(dsgn: &str, patterns: &[String]) -> usize {
+    let mut queue: VecDeque<String> = VecDeque::new();
+    queue.push_back(dsgn.to_string());
+    let mut found = 0;
+    while let Some(design) = queue.pop_back()  {
+        if cancel::cancelled() {
+            break;
+        }
+        for pattern in patterns.iter() {
+            if design.starts_with(pattern) {
+                let mut d = design.clone();
+                d.replace_range(0..pattern.len(), "");
+                if d.is_empty() {
+                    found += 1;
+                } else {
+                    queue.push_back(d);
+                }
+            }
+        }
+    }
+    trace!(design = dsgn, found);
+    found
+}
+
+fn find_all_possible_patterns(dsgn: &str, patterns: &[(String, usize)]) -> usize {
+    let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+    queue.push_back((dsgn.to_string(), 1));
+    let mut found = 0;
+    while let Some((design, possible_pat)) = queue.pop_back()  {
+        if cancel::cancelled() {
+            break;
+        }
+        for (pattern, subpat) in patterns.iter() {
+            if design.starts_with(pattern) {
+                let mut d = design.clone();
+                d.replace_range(0..pattern.len(), "");
+                queue.push_back((d, possible_pat * subpat));
+            }
+        }
+        if design.is_empty() {
+            found = possible_pat;
+            break;
+        }
+    }
+    trace!(design = dsgn, found);
+    found
+}
+
+fn first_task(patterns: &[String], designs: &[String]) -> usize {
+    let mut possible = 0;
+    for design in designs {
+        let found = find_pattern(design, patterns);
+        if found {
+            possible += 1;
+        }
+    }
+    possible
+}
+
+fn second_task(patterns: &[String], designs: &[String]) -> usize {
+    let mut possible = 0;
+    let mut patterns_with_subpatterns = Vec::new();
+    debug_span!("subpatterns").in_scope(|| {
+        for pat in patterns.iter() {
+            let subpatterns = find_all_patterns(pat, patterns);
+            patterns_with_subpatterns.push((pat.clone(), subpatterns));
+        }
+    });
+    let _designs = debug_span!("designs").entered();
+    for design in designs {
+        possible += find_all_possible_patterns(design, &patterns_with_subpatterns);
+    }
+    possible
+}
+
+/// Towel patterns, longest first, and the designs to build from them.
+#[derive(Debug, Clone)]
+pub struct Onsen {
+    pub patterns: Vec<String>,
+    pub designs: Vec<String>,
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Onsen;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (mut patterns, designs) = extract(input);
+        patterns.sort_by_key(|pattern| Reverse(pattern.len()));
+        Ok(Onsen { patterns, designs })
+    }
+
+    fn part1(onsen: &Self::Input) -> Answer {
+        first_task(&onsen.patterns, &onsen.designs).into()
+    }
+
+    fn part2(onsen: &Self::Input) -> Answer {
+        second_task(&onsen.patterns, &onsen.designs).into()
+    }
+}
+
+aoc_common::example_tests!(Day19: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_from_str(input: &str) -> (Vec<String>, Vec<String>) {
+        extract(input)
+    }
+    
+    const INPUT: &str = 
+r#"r, wr, b, g, bwu, rb, gb, br, wut, rrbgbr
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+bwut
+"#;
+
+    #[test]
+    fn first_task_with_first_sample() {
+        let (patterns, designs) = read_from_str(INPUT);
+        let mut patterns_desc = patterns.clone();
+        patterns_desc.sort_by_key(|pattern| Reverse(pattern.len()));
+
+        let result = first_task(&patterns_desc, &designs);
+        assert_eq!(result, 7);
+    }
+
+    #[test]
+    fn second_task_with_first_sample() {
+        let (patterns, designs) = read_from_str(INPUT);
+
+        let result = second_task(&patterns, &designs);
+        // the puzzle's 16, plus `rrbgbr` as one whole pattern and `b` + `wut`
+        assert_eq!(result, 18);
+    }
+
+}
@@ -0,0 +1,255 @@
+//! Why each report is safe or not: the first step that breaks the rules
+//! and, with the dampener, the levels whose removal makes it safe.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::rules::{Direction, Rules, Verdict};
+use crate::Reports;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// Two equal levels in a row.
+    ZeroDiff,
+    StepTooSmall,
+    StepTooLarge,
+    /// The levels turned around after going the other way.
+    DirectionFlip,
+    /// The levels go against the required direction.
+    WrongDirection,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Rule::ZeroDiff => "zero diff",
+            Rule::StepTooSmall => "step too small",
+            Rule::StepTooLarge => "step too large",
+            Rule::DirectionFlip => "direction flip",
+            Rule::WrongDirection => "wrong direction",
+        })
+    }
+}
+
+/// The first step, from level `index` to the next, that breaks a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub index: usize,
+    pub pair: (i64, i64),
+    pub rule: Rule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Safe,
+    /// Safe once the dampener removed some levels.
+    Dampened,
+    Unsafe,
+}
+
+/// A level the dampener removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Removal {
+    pub index: usize,
+    pub level: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    /// 1-based, like the line of the input.
+    pub report: usize,
+    pub levels: Vec<i64>,
+    pub verdict: Outcome,
+    /// How the report breaks the rules before any dampening.
+    pub violation: Option<Violation>,
+    pub removed: Vec<Removal>,
+}
+
+/// The first step of `levels` that breaks the rules. Without a required
+/// direction the first step that moves decides it, as the puzzle reads.
+pub fn first_violation(rules: &Rules, levels: &[i64]) -> Option<Violation> {
+    let mut increasing = match rules.direction {
+        Direction::Increasing => Some(true),
+        Direction::Decreasing => Some(false),
+        Direction::Either => None,
+    };
+
+    levels.windows(2).enumerate().find_map(|(index, pair)| {
+        let (from, to) = (pair[0], pair[1]);
+        let up = match to.cmp(&from) {
+            Ordering::Greater => Some(true),
+            Ordering::Less => Some(false),
+            Ordering::Equal => None,
+        };
+        let size = i128::from(to.abs_diff(from));
+        let rule = if up.is_none() && rules.min_step > 0 {
+            Some(Rule::ZeroDiff)
+        } else if up.is_some_and(|up| increasing.is_some_and(|increasing| increasing != up)) {
+            match rules.direction {
+                Direction::Either => Some(Rule::DirectionFlip),
+                _ => Some(Rule::WrongDirection),
+            }
+        } else if size < i128::from(rules.min_step) {
+            Some(Rule::StepTooSmall)
+        } else if size > i128::from(rules.max_step) {
+            Some(Rule::StepTooLarge)
+        } else {
+            None
+        };
+        if let Some(up) = up {
+            increasing.get_or_insert(up);
+        }
+        rule.map(|rule| Violation {
+            index,
+            pair: (from, to),
+            rule,
+        })
+    })
+}
+
+/// Explains every report, with `tolerance` levels the dampener may remove.
+pub fn explain(reports: &Reports, tolerance: usize) -> Vec<Explanation> {
+    reports
+        .reports
+        .iter()
+        .zip(reports.verdicts(tolerance))
+        .enumerate()
+        .map(|(index, (levels, verdict))| {
+            let (outcome, removed) = match verdict {
+                Verdict::Safe => (Outcome::Safe, Vec::new()),
+                Verdict::Dampened(removed) => (Outcome::Dampened, removed),
+                Verdict::Unsafe => (Outcome::Unsafe, Vec::new()),
+            };
+            Explanation {
+                report: index + 1,
+                levels: levels.clone(),
+                verdict: outcome,
+                violation: first_violation(&reports.rules, levels),
+                removed: removed
+                    .into_iter()
+                    .map(|index| Removal {
+                        index,
+                        level: levels[index],
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let levels: Vec<String> = self.levels.iter().map(i64::to_string).collect();
+        write!(f, "report {}: {}: ", self.report, levels.join(" "))?;
+        match self.verdict {
+            Outcome::Safe => write!(f, "safe")?,
+            Outcome::Unsafe => write!(f, "unsafe")?,
+            Outcome::Dampened => {
+                let removed: Vec<String> = self
+                    .removed
+                    .iter()
+                    .map(|removal| format!("{} at index {}", removal.level, removal.index))
+                    .collect();
+                write!(f, "safe without {}", removed.join(", "))?;
+            }
+        }
+        if let Some(violation) = self.violation {
+            write!(
+                f,
+                "; {} from {} to {} at index {}",
+                violation.rule, violation.pair.0, violation.pair.1, violation.index
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day2;
+    use aoc_common::{Params, Solution};
+
+    const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+    fn rule_of(rules: Rules, levels: &[i64]) -> Option<(usize, Rule)> {
+        first_violation(&rules, levels).map(|violation| (violation.index, violation.rule))
+    }
+
+    #[test]
+    fn names_the_first_broken_rule() {
+        let rules = Rules::default();
+        assert_eq!(rule_of(rules, &[7, 6, 4, 2, 1]), None);
+        assert_eq!(rule_of(rules, &[1, 2, 7, 8, 9]), Some((1, Rule::StepTooLarge)));
+        assert_eq!(rule_of(rules, &[1, 3, 2, 4, 5]), Some((1, Rule::DirectionFlip)));
+        assert_eq!(rule_of(rules, &[8, 6, 4, 4, 1]), Some((2, Rule::ZeroDiff)));
+
+        let rules = Rules {
+            min_step: 2,
+            direction: Direction::Decreasing,
+            ..rules
+        };
+        assert_eq!(rule_of(rules, &[9, 7, 8]), Some((1, Rule::WrongDirection)));
+        assert_eq!(rule_of(rules, &[9, 7, 6]), Some((1, Rule::StepTooSmall)));
+    }
+
+    #[test]
+    fn levels_at_the_ends_of_i64_do_not_overflow() {
+        let rules = Rules::default();
+        assert_eq!(rule_of(rules, &[i64::MIN, i64::MAX]), Some((0, Rule::StepTooLarge)));
+        assert_eq!(rule_of(rules, &[i64::MAX, i64::MIN]), Some((0, Rule::StepTooLarge)));
+        let wide = Rules {
+            max_step: i64::MAX,
+            ..rules
+        };
+        assert_eq!(rule_of(wide, &[i64::MIN, -1, i64::MAX - 1]), None);
+        assert_eq!(rule_of(wide, &[i64::MIN, i64::MIN + 1, i64::MIN]), Some((1, Rule::DirectionFlip)));
+
+        let input = format!("{} {}\n", i64::MIN, i64::MAX);
+        let reports = Day2::parse(&input).unwrap();
+        assert_eq!(explain(&reports, 1)[0].verdict, Outcome::Dampened);
+    }
+
+    #[test]
+    fn explains_the_example_with_the_dampener() {
+        let reports = Day2::parse(EXAMPLE).unwrap();
+        let explained = explain(&reports, 1);
+        let verdicts: Vec<Outcome> = explained.iter().map(|explanation| explanation.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                Outcome::Safe,
+                Outcome::Unsafe,
+                Outcome::Unsafe,
+                Outcome::Dampened,
+                Outcome::Dampened,
+                Outcome::Safe
+            ]
+        );
+        assert_eq!(
+            explained[3].to_string(),
+            "report 4: 1 3 2 4 5: safe without 2 at index 2; direction flip from 3 to 2 at index 1"
+        );
+        assert_eq!(
+            explained[1].to_string(),
+            "report 2: 1 2 7 8 9: unsafe; step too large from 2 to 7 at index 1"
+        );
+        assert_eq!(explained[0].to_string(), "report 1: 7 6 4 2 1: safe");
+    }
+
+    #[test]
+    fn explanations_serialize_to_json() {
+        let reports = Day2::parse_with("8 6 4 4 1\n", &Params::new()).unwrap();
+        let json = serde_json::to_string(&explain(&reports, 1)[0]).unwrap();
+        assert_eq!(
+            json,
+            "{\"report\":1,\"levels\":[8,6,4,4,1],\"verdict\":\"dampened\",\
+             \"violation\":{\"index\":2,\"pair\":[4,4],\"rule\":\"zero_diff\"},\
+             \"removed\":[{\"index\":3,\"level\":4}]}"
+        );
+    }
+}
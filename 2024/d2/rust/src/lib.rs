@@ -0,0 +1,131 @@
+use aoc_common::parse::{numbered_lines, parse_token};
+use aoc_common::{Answer, Params, ParseError, Solution};
+
+pub mod explain;
+pub mod rules;
+
+pub use rules::{Direction, Rules, Verdict};
+
+fn read_lists(input: &str, strict: bool) -> Result<Vec<Vec<i64>>, ParseError> {
+    let mut lists = Vec::new();
+
+    for (line_number, line) in numbered_lines(input) {
+        let numbers: Vec<i64> = if strict {
+            line.split_whitespace()
+                .map(|s| parse_token(line_number, line, s, "level"))
+                .collect::<Result<_, _>>()?
+        } else {
+            line.split_whitespace()
+                .filter_map(|s| s.parse().ok())
+                .collect()
+        };
+        lists.push(numbers);
+    }
+
+    Ok(lists)
+}
+
+/// The reports with the rules they are checked against. Part 1 applies
+/// the rules without the dampener, part 2 with `dampener` removals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reports {
+    pub reports: Vec<Vec<i64>>,
+    pub rules: Rules,
+    pub dampener: usize,
+}
+
+impl Reports {
+    fn read(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let defaults = Rules::default();
+        let rules = Rules {
+            min_step: params.get("min_step")?.unwrap_or(defaults.min_step),
+            max_step: params.get("max_step")?.unwrap_or(defaults.max_step),
+            direction: params.get("direction")?.unwrap_or(defaults.direction),
+            tolerance: 0,
+        };
+        if rules.min_step < 0 || rules.min_step > rules.max_step {
+            return Err(ParseError::new(format!(
+                "steps of {} to {} levels can't be met",
+                rules.min_step, rules.max_step
+            )));
+        }
+        Ok(Reports {
+            reports: read_lists(input, params.is_strict())?,
+            rules,
+            dampener: params.get("dampener")?.unwrap_or(1),
+        })
+    }
+
+    /// The verdict on every report, with `tolerance` removable levels.
+    pub fn verdicts(&self, tolerance: usize) -> Vec<Verdict> {
+        let rules = self.rules.with_tolerance(tolerance);
+        self.reports.iter().map(|levels| rules.check(levels)).collect()
+    }
+
+    fn safe(&self, tolerance: usize) -> usize {
+        self.verdicts(tolerance).iter().filter(|verdict| verdict.is_safe()).count()
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Reports;
+
+    const PARAMS: &'static [&'static str] = &["min_step", "max_step", "direction", "dampener"];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Reports::read(input, params)
+    }
+
+    fn part1(reports: &Self::Input) -> Answer {
+        reports.safe(0).into()
+    }
+
+    fn part2(reports: &Self::Input) -> Answer {
+        reports.safe(reports.dampener).into()
+    }
+}
+
+aoc_common::example_tests!(Day2: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_parse_reports_unreadable_level() {
+        let input = "7 6 4 2 1\n1 2 7 8 9O\n";
+        assert_eq!(Day2::parse(input).unwrap().reports[1], vec![1, 2, 7, 8]);
+
+        let error = Day2::parse_with(input, &Params::new().strict(true)).unwrap_err();
+        assert_eq!(error.to_string(), "2:9: expected level, found `9O`");
+    }
+
+    #[test]
+    fn verdict_per_report() {
+        let reports = Day2::parse("7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5\n").unwrap();
+        assert_eq!(reports.verdicts(0), vec![Verdict::Safe, Verdict::Unsafe, Verdict::Unsafe]);
+        assert_eq!(
+            reports.verdicts(1),
+            vec![Verdict::Safe, Verdict::Unsafe, Verdict::Dampened(vec![2])]
+        );
+    }
+
+    #[test]
+    fn params_change_the_rules() {
+        let input = "1 5 9 13\n13 9 5 1\n1 5 12 10\n";
+        let params = Params::new().with("max_step", 4).with("direction", "increasing").with("dampener", 2);
+        let reports = Day2::parse_with(input, &params).unwrap();
+        assert_eq!(Day2::part1(&reports), Answer::Number(1));
+        assert_eq!(Day2::part2(&reports), Answer::Number(2));
+
+        let error = Day2::parse_with(input, &Params::new().with("min_step", 4).with("max_step", 2)).unwrap_err();
+        assert_eq!(error.to_string(), "steps of 4 to 2 levels can't be met");
+        assert!(Day2::parse_with(input, &Params::new().with("direction", "sideways")).is_err());
+    }
+}
@@ -1,90 +1,53 @@
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
-use log::{info, LevelFilter};
-use env_logger::Builder;
-
-fn read_lists(filename: &str) -> io::Result<Vec<Vec<i32>>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
+use aoc_common::{InputProvider, Params, Solution};
+use clap::Parser;
+use day2::Day2;
+use tracing::info;
+
+#[derive(Parser)]
+struct Args {
+    /// Also say why each report is safe or not, for part 1 or 2
+    #[arg(long, value_name = "PART", num_args = 0..=1, default_missing_value = "2",
+          value_parser = clap::value_parser!(u8).range(1..=2))]
+    explain: Option<u8>,
+    /// Print the explanations as one JSON object per report
+    #[arg(long, requires = "explain")]
+    json: bool,
+    /// Rule parameter, such as `dampener=2` or `direction=increasing`
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+}
 
-    let mut lists = Vec::new();
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    aoc_common::init_logger();
 
-    for line in reader.lines() {
-        let line = line?;
-        let numbers: Vec<i32> = line
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
-        lists.push(numbers);
+    let params = Params::from_pairs(&args.params)?;
+    if let Some(unknown) = params.names().find(|name| !Day2::PARAMS.contains(name)) {
+        let known = Day2::PARAMS.join(", ");
+        return Err(format!("unknown parameter `{}` (known: {})", unknown, known).into());
     }
 
-    Ok(lists)
-}
-
-fn calculate_safe_levels(list: &[i32]) -> Vec<bool> {
-    let mut safe_levels = Vec::new();
-    let mut maybe_increasing = None;
+    let input = InputProvider::from_env().fetch(2024, 2)?;
+    info!("Read source data from {}", input.path.display());
 
-    for window in list.windows(2) {
-        if let [first, second] = window {
-            let diff = second - first;
-            let abs_diff = diff.abs();
+    let reports = Day2::parse_with(&input.text, &params).map_err(|e| e.with_file(&input.path))?;
 
-            if maybe_increasing.is_none() && diff != 0 {
-                maybe_increasing = Some(diff > 0);
-            }
+    let first_result = Day2::part1(&reports);
+    aoc_common::print_result(1, "Number of safe reports", first_result);
 
-            let is_safe = abs_diff >= 1 && abs_diff <= 3 && maybe_increasing.unwrap_or(true) == (diff > 0);
-            safe_levels.push(is_safe);
-        }
-    }
-
-    safe_levels
-}
-
-fn first_task(lists: &[Vec<i32>]) -> usize {
-    lists.iter()
-        .filter(|list| calculate_safe_levels(list).iter().all(|&x| x))
-        .count()
-}
+    let second_result = Day2::part2(&reports);
+    aoc_common::print_result(2, "Safe reports with single bad level", second_result);
 
-fn second_task(lists: &[Vec<i32>]) -> usize {
-    lists.iter()
-        .filter(|list| {
-            let safe_levels = calculate_safe_levels(list);
-            if safe_levels.contains(&false) {
-                list.iter().enumerate().any(|(i, _)| {
-                    let new_list: Vec<i32> = list.iter().enumerate()
-                        .filter(|&(j, _)| j != i)
-                        .map(|(_, &val)| val)
-                        .collect();
-                    calculate_safe_levels(&new_list).iter().all(|&x| x)
-                })
+    if let Some(part) = args.explain {
+        let tolerance = if part == 1 { 0 } else { reports.dampener };
+        for explanation in day2::explain::explain(&reports, tolerance) {
+            if args.json {
+                println!("{}", serde_json::to_string(&explanation)?);
             } else {
-                safe_levels.iter().all(|&x| x)
+                println!("{}", explanation);
             }
-        })
-        .count()
-}
-
-fn main() -> io::Result<()> {
-    // Initialize logger with environment variable
-    Builder::new()
-        .parse_env("LOG_LEVEL")
-        .filter_level(LevelFilter::Info)
-        .init();
-
-    let source_file_name = "./d2/input.txt";
-
-    info!("Read source data from {}", source_file_name);
-
-    let lists = read_lists(source_file_name)?;
-
-    let first_result = first_task(&lists);
-    println!("1st Task: Number of safe reports: {}", first_result);
-
-    let second_result = second_task(&lists);
-    println!("2nd Task: Safe reports with single bad level: {}", second_result);
+        }
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
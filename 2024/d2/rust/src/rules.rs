@@ -0,0 +1,229 @@
+//! The safety rules of a report, with a dampener that may drop levels.
+//!
+//! A report is safe when every step between neighbouring levels is within
+//! `min_step..=max_step` and goes in the required direction. With a
+//! tolerance of `k` the report may also be made safe by removing up to `k`
+//! levels; the check keeps the longest valid chain of levels in O(n·k).
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// All steps go the same way, whichever it is.
+    Either,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "increasing" => Ok(Direction::Increasing),
+            "decreasing" => Ok(Direction::Decreasing),
+            "either" => Ok(Direction::Either),
+            _ => Err(format!("unknown direction `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Increasing => "increasing",
+            Direction::Decreasing => "decreasing",
+            Direction::Either => "either",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub min_step: i64,
+    pub max_step: i64,
+    pub direction: Direction,
+    /// How many levels the dampener may remove.
+    pub tolerance: usize,
+}
+
+impl Default for Rules {
+    /// The puzzle's rules: steps of 1 to 3 in either direction, no dampener.
+    fn default() -> Self {
+        Rules {
+            min_step: 1,
+            max_step: 3,
+            direction: Direction::Either,
+            tolerance: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the levels at these indices are removed.
+    Dampened(Vec<usize>),
+    Unsafe,
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        *self != Verdict::Unsafe
+    }
+}
+
+impl Rules {
+    pub fn with_tolerance(self, tolerance: usize) -> Self {
+        Rules { tolerance, ..self }
+    }
+
+    /// Whether the step from `from` to `to` is allowed going up, or down.
+    pub fn allows(&self, from: i64, to: i64, increasing: bool) -> bool {
+        // levels span all of i64, so their difference needs i128
+        let (from, to) = (i128::from(from), i128::from(to));
+        let step = if increasing { to - from } else { from - to };
+        (i128::from(self.min_step)..=i128::from(self.max_step)).contains(&step)
+    }
+
+    pub fn check(&self, levels: &[i64]) -> Verdict {
+        let removed = match self.direction {
+            Direction::Increasing => self.fewest_removals(levels, true),
+            Direction::Decreasing => self.fewest_removals(levels, false),
+            Direction::Either => {
+                match (self.fewest_removals(levels, true), self.fewest_removals(levels, false)) {
+                    (Some(up), Some(down)) if down.len() < up.len() => Some(down),
+                    (Some(up), _) => Some(up),
+                    (None, down) => down,
+                }
+            }
+        };
+        match removed {
+            Some(removed) if removed.is_empty() => Verdict::Safe,
+            Some(removed) => Verdict::Dampened(removed),
+            None => Verdict::Unsafe,
+        }
+    }
+
+    /// Indices of the fewest levels to remove so the rest steps one way,
+    /// or None when that takes more than the tolerance.
+    fn fewest_removals(&self, levels: &[i64], increasing: bool) -> Option<Vec<usize>> {
+        let count = levels.len();
+        let reach = self.tolerance + 1;
+        // removals[j]: fewest levels removed before j in a valid chain
+        // ending at j; two kept levels are at most `reach` apart
+        let mut removals: Vec<Option<usize>> = vec![None; count];
+        let mut previous: Vec<Option<usize>> = vec![None; count];
+
+        for j in 0..count {
+            if j <= self.tolerance {
+                removals[j] = Some(j);
+            }
+            for i in j.saturating_sub(reach)..j {
+                let Some(before) = removals[i] else { continue };
+                let total = before + (j - i - 1);
+                if total <= self.tolerance
+                    && removals[j].is_none_or(|best| total < best)
+                    && self.allows(levels[i], levels[j], increasing)
+                {
+                    removals[j] = Some(total);
+                    previous[j] = Some(i);
+                }
+            }
+        }
+
+        let end = (count.saturating_sub(reach)..count)
+            .filter_map(|j| Some((j, removals[j]? + (count - 1 - j))))
+            .filter(|&(_, total)| total <= self.tolerance)
+            .min_by_key(|&(_, total)| total);
+        let Some((end, _)) = end else {
+            // only an empty report has no last level to keep
+            return (count == 0).then(Vec::new);
+        };
+
+        let mut kept = vec![false; count];
+        let mut at = Some(end);
+        while let Some(index) = at {
+            kept[index] = true;
+            at = previous[index];
+        }
+        Some((0..count).filter(|&index| !kept[index]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verdict(rules: Rules, levels: &[i64]) -> Verdict {
+        rules.check(levels)
+    }
+
+    #[test]
+    fn puzzle_rules_without_dampener() {
+        let rules = Rules::default();
+        assert_eq!(verdict(rules, &[7, 6, 4, 2, 1]), Verdict::Safe);
+        assert_eq!(verdict(rules, &[1, 2, 7, 8, 9]), Verdict::Unsafe);
+        assert_eq!(verdict(rules, &[1, 3, 2, 4, 5]), Verdict::Unsafe);
+        assert_eq!(verdict(rules, &[8, 6, 4, 4, 1]), Verdict::Unsafe);
+        assert_eq!(verdict(rules, &[]), Verdict::Safe);
+        assert_eq!(verdict(rules, &[5]), Verdict::Safe);
+    }
+
+    #[test]
+    fn dampener_names_the_removed_levels() {
+        let rules = Rules::default().with_tolerance(1);
+        assert_eq!(verdict(rules, &[1, 3, 2, 4, 5]), Verdict::Dampened(vec![2]));
+        assert_eq!(verdict(rules, &[8, 6, 4, 4, 1]), Verdict::Dampened(vec![3]));
+        assert_eq!(verdict(rules, &[9, 1, 2, 3]), Verdict::Dampened(vec![0]));
+        assert_eq!(verdict(rules, &[1, 2, 3, 9]), Verdict::Dampened(vec![3]));
+        assert_eq!(verdict(rules, &[9, 7, 6, 2, 1]), Verdict::Unsafe);
+    }
+
+    #[test]
+    fn larger_tolerance_removes_runs_of_levels() {
+        let levels = [1, 2, 30, 40, 3, 4];
+        assert_eq!(verdict(Rules::default().with_tolerance(1), &levels), Verdict::Unsafe);
+        assert_eq!(verdict(Rules::default().with_tolerance(2), &levels), Verdict::Dampened(vec![2, 3]));
+        assert_eq!(verdict(Rules::default().with_tolerance(2), &[50, 60, 1, 2, 3]), Verdict::Dampened(vec![0, 1]));
+    }
+
+    #[test]
+    fn direction_and_step_bounds_are_configurable() {
+        let rules = Rules {
+            min_step: 0,
+            max_step: 5,
+            direction: Direction::Increasing,
+            tolerance: 0,
+        };
+        assert_eq!(verdict(rules, &[1, 1, 6, 8]), Verdict::Safe);
+        assert_eq!(verdict(rules, &[8, 6, 1]), Verdict::Unsafe);
+        let rules = Rules {
+            direction: Direction::Decreasing,
+            ..rules
+        };
+        assert_eq!(verdict(rules, &[8, 6, 1]), Verdict::Safe);
+        assert_eq!(verdict(rules.with_tolerance(1), &[8, 6, 7, 1]), Verdict::Dampened(vec![2]));
+    }
+
+    #[test]
+    fn levels_at_the_ends_of_i64_do_not_overflow() {
+        let rules = Rules {
+            max_step: i64::MAX,
+            ..Rules::default()
+        };
+        assert_eq!(verdict(Rules::default(), &[i64::MIN, i64::MAX]), Verdict::Unsafe);
+        assert_eq!(verdict(rules, &[i64::MIN, i64::MAX]), Verdict::Unsafe);
+        assert_eq!(verdict(rules, &[i64::MIN, -1, i64::MAX - 1]), Verdict::Safe);
+        assert_eq!(verdict(rules, &[i64::MAX, 0, i64::MIN + 1]), Verdict::Safe);
+        assert_eq!(verdict(rules.with_tolerance(1), &[i64::MIN, i64::MAX, 0]), Verdict::Dampened(vec![0]));
+    }
+
+    #[test]
+    fn either_direction_keeps_the_cheaper_one() {
+        let rules = Rules::default().with_tolerance(2);
+        assert_eq!(verdict(rules, &[5, 4, 3, 4, 5, 6, 7]), Verdict::Dampened(vec![0, 1]));
+        assert_eq!(verdict(rules, &[5, 6, 5, 4, 3]), Verdict::Dampened(vec![0]));
+    }
+}
@@ -0,0 +1,400 @@
+//! A scanner and interpreter for the corrupted memory.
+//!
+//! The instructions it knows come from a `Registry`: each has a name, a
+//! number of arguments with at most so many digits, and a handler that
+//! updates the machine `State`. The scanner only uses that description, so
+//! new instructions need no changes to it.
+
+use std::fmt;
+
+/// What the instructions work on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub sum: i64,
+    /// Whether `mul` instructions count, as `do()` and `don't()` set it.
+    pub enabled: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State { sum: 0, enabled: true }
+    }
+}
+
+pub type Handler = fn(&mut State, &[i64]);
+
+/// An instruction written as `name(1,22,333)`.
+#[derive(Clone, Copy)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Most digits an argument may have, up to 18.
+    pub max_digits: usize,
+    pub handler: Handler,
+}
+
+impl Instruction {
+    pub const MUL: Instruction = Instruction {
+        name: "mul",
+        arity: 2,
+        max_digits: 3,
+        handler: |state, args| {
+            if state.enabled {
+                state.sum += args[0] * args[1];
+            }
+        },
+    };
+
+    pub const DO: Instruction = Instruction {
+        name: "do",
+        arity: 0,
+        max_digits: 0,
+        handler: |state, _| state.enabled = true,
+    };
+
+    pub const DONT: Instruction = Instruction {
+        name: "don't",
+        arity: 0,
+        max_digits: 0,
+        handler: |state, _| state.enabled = false,
+    };
+}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Instruction")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .field("max_digits", &self.max_digits)
+            .finish()
+    }
+}
+
+/// An instruction found in memory.
+#[derive(Debug, Clone)]
+pub struct Call<'a> {
+    pub instruction: &'a Instruction,
+    /// Byte offset of the instruction's name.
+    pub offset: usize,
+    /// Length of the whole call, from the name to the closing parenthesis.
+    pub len: usize,
+    pub args: Vec<i64>,
+}
+
+impl fmt::Display for Call<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let args: Vec<String> = self.args.iter().map(i64::to_string).collect();
+        write!(f, "{}({})", self.instruction.name, args.join(","))
+    }
+}
+
+/// Why something that starts like an instruction was not read as one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    SpaceBeforeParen,
+    /// Arguments are counted from 1; `found` is None at the end of memory.
+    ExpectedDigit { argument: usize, found: Option<u8> },
+    TooManyDigits { argument: usize, digits: usize, max: usize },
+    ExpectedComma { argument: usize, found: Option<u8> },
+    ExpectedClose { found: Option<u8> },
+}
+
+fn found(byte: Option<u8>) -> String {
+    match byte {
+        Some(b'\n') => "a line break".to_string(),
+        Some(byte) => format!("`{}`", byte.escape_ascii()),
+        None => "the end of memory".to_string(),
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Reason::SpaceBeforeParen => write!(f, "space between the name and `(`"),
+            Reason::ExpectedDigit { argument, found: byte } => {
+                write!(f, "expected a digit for argument {}, found {}", argument, found(byte))
+            }
+            Reason::TooManyDigits { argument, digits, max } => {
+                write!(f, "argument {} has {} digits, at most {} allowed", argument, digits, max)
+            }
+            Reason::ExpectedComma { argument, found: byte } => {
+                write!(f, "expected `,` after argument {}, found {}", argument, found(byte))
+            }
+            Reason::ExpectedClose { found: byte } => write!(f, "expected `)`, found {}", found(byte)),
+        }
+    }
+}
+
+/// An instruction name followed by `(` that doesn't make a valid call.
+#[derive(Debug, Clone)]
+pub struct NearMiss<'a> {
+    pub instruction: &'a Instruction,
+    pub offset: usize,
+    /// The bytes from the name to the closing parenthesis when there is one
+    /// close by on the same line, or else to where reading failed.
+    pub fragment: &'a [u8],
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone)]
+pub enum Token<'a> {
+    Call(Call<'a>),
+    NearMiss(NearMiss<'a>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    instructions: Vec<Instruction>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// `mul`, `do` and `don't`.
+    pub fn builtin() -> Self {
+        Registry::new()
+            .with(Instruction::MUL)
+            .with(Instruction::DO)
+            .with(Instruction::DONT)
+    }
+
+    /// Adds an instruction; earlier ones win when two match at one place.
+    pub fn with(mut self, instruction: Instruction) -> Self {
+        assert!(instruction.max_digits <= 18, "arguments of {} don't fit in i64", instruction.name);
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Every call and near miss in memory, in order. Bytes that don't
+    /// start a valid call are skipped one at a time, so calls may start
+    /// inside broken ones.
+    pub fn tokens<'a>(&'a self, memory: &'a [u8]) -> impl Iterator<Item = Token<'a>> + 'a {
+        let mut offset = 0;
+        std::iter::from_fn(move || {
+            while offset < memory.len() {
+                let mut near_miss = None;
+                for instruction in self.instructions.iter() {
+                    match read_at(instruction, memory, offset) {
+                        Some(Ok(call)) => {
+                            offset += call.len;
+                            return Some(Token::Call(call));
+                        }
+                        Some(Err((reason, failed_at))) => {
+                            near_miss.get_or_insert(NearMiss {
+                                instruction,
+                                offset,
+                                fragment: fragment(memory, instruction.name, offset, failed_at),
+                                reason,
+                            });
+                        }
+                        None => {}
+                    }
+                }
+                offset += 1;
+                if let Some(near_miss) = near_miss {
+                    return Some(Token::NearMiss(near_miss));
+                }
+            }
+            None
+        })
+    }
+
+    /// Every call in memory, in order.
+    pub fn scan<'a>(&'a self, memory: &'a [u8]) -> impl Iterator<Item = Call<'a>> + 'a {
+        self.tokens(memory).filter_map(|token| match token {
+            Token::Call(call) => Some(call),
+            Token::NearMiss(_) => None,
+        })
+    }
+
+    /// Runs every call in memory from the default state.
+    pub fn run(&self, memory: &[u8]) -> State {
+        let mut state = State::default();
+        for call in self.scan(memory) {
+            (call.instruction.handler)(&mut state, &call.args);
+        }
+        state
+    }
+}
+
+/// How far past a near miss to look for its closing parenthesis.
+const FRAGMENT_REACH: usize = 24;
+
+/// The near miss at `offset`, up to its closing parenthesis if that comes
+/// before the end of the line and before the name shows up again.
+fn fragment<'a>(memory: &'a [u8], name: &str, offset: usize, failed_at: usize) -> &'a [u8] {
+    let window = &memory[offset..memory.len().min(offset + FRAGMENT_REACH)];
+    let line = window.split(|&byte| byte == b'\n').next().unwrap_or_default();
+    let line = match line[1..].windows(name.len()).position(|bytes| bytes == name.as_bytes()) {
+        Some(next) => &line[..next + 1],
+        None => line,
+    };
+    let end = match line.iter().position(|&byte| byte == b')') {
+        Some(close) if offset + close >= failed_at => close + 1,
+        _ => (failed_at + 1 - offset).min(line.len()),
+    };
+    &memory[offset..offset + end]
+}
+
+/// Reads `instruction` with its arguments at `offset`. None when the name
+/// and a `(` aren't there; otherwise the call, or why it is broken and the
+/// offset where reading failed.
+fn read_at<'a>(
+    instruction: &'a Instruction,
+    memory: &[u8],
+    offset: usize,
+) -> Option<Result<Call<'a>, (Reason, usize)>> {
+    let name_end = offset + instruction.name.len();
+    if !memory[offset..].starts_with(instruction.name.as_bytes()) {
+        return None;
+    }
+    let spaces = memory[name_end..].iter().take_while(|&&byte| byte == b' ' || byte == b'\t').count();
+    if memory.get(name_end + spaces) != Some(&b'(') {
+        return None;
+    }
+    if spaces > 0 {
+        return Some(Err((Reason::SpaceBeforeParen, name_end)));
+    }
+
+    let mut at = name_end + 1;
+    let mut args = Vec::with_capacity(instruction.arity);
+    for argument in 1..=instruction.arity {
+        if argument > 1 {
+            if memory.get(at) != Some(&b',') {
+                let reason = Reason::ExpectedComma {
+                    argument: argument - 1,
+                    found: memory.get(at).copied(),
+                };
+                return Some(Err((reason, at)));
+            }
+            at += 1;
+        }
+        let digits = memory[at..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 0 {
+            let reason = Reason::ExpectedDigit {
+                argument,
+                found: memory.get(at).copied(),
+            };
+            return Some(Err((reason, at)));
+        }
+        if digits > instruction.max_digits {
+            let reason = Reason::TooManyDigits {
+                argument,
+                digits,
+                max: instruction.max_digits,
+            };
+            return Some(Err((reason, at + digits - 1)));
+        }
+        let number = memory[at..at + digits]
+            .iter()
+            .fold(0, |number, digit| number * 10 + i64::from(digit - b'0'));
+        args.push(number);
+        at += digits;
+    }
+
+    if memory.get(at) != Some(&b')') {
+        return Some(Err((Reason::ExpectedClose { found: memory.get(at).copied() }, at)));
+    }
+    Some(Ok(Call {
+        instruction,
+        offset,
+        len: at + 1 - offset,
+        args,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(registry: &Registry, memory: &str) -> Vec<String> {
+        registry
+            .scan(memory.as_bytes())
+            .map(|call| format!("{}@{}{:?}", call.instruction.name, call.offset, call.args))
+            .collect()
+    }
+
+    #[test]
+    fn scanner_reads_calls_and_skips_broken_ones() {
+        let registry = Registry::builtin();
+        assert_eq!(
+            names(&registry, "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"),
+            vec![
+                "mul@1[2, 4]",
+                "don't@20[]",
+                "mul@28[5, 5]",
+                "mul@48[11, 8]",
+                "do@59[]",
+                "mul@64[8, 5]"
+            ]
+        );
+        assert!(names(&registry, "mul(1234,5) mul(1,2 mul(,3) mul ( 2,3) do(1)").is_empty());
+        assert_eq!(names(&registry, "mulmul(3,4)"), vec!["mul@3[3, 4]"]);
+    }
+
+    #[test]
+    fn handlers_update_the_state() {
+        let state = Registry::builtin().run(b"mul(2,3)don't()mul(100,100)do()mul(999,999)");
+        assert_eq!(state, State { sum: 6 + 998_001, enabled: true });
+    }
+
+    #[test]
+    fn new_instructions_need_no_scanner_changes() {
+        let registry = Registry::builtin()
+            .with(Instruction {
+                name: "add",
+                arity: 2,
+                max_digits: 3,
+                handler: |state, args| state.sum += args[0] + args[1],
+            })
+            .with(Instruction {
+                name: "sub",
+                arity: 1,
+                max_digits: 12,
+                handler: |state, args| state.sum -= args[0],
+            });
+        let state = registry.run(b"add(1,2)?sub(123456789012)mul(2,2)add(1,2,3)");
+        assert_eq!(state.sum, 3 - 123_456_789_012 + 4);
+    }
+
+    fn near_misses(memory: &str) -> Vec<(usize, String, String)> {
+        Registry::builtin()
+            .tokens(memory.as_bytes())
+            .filter_map(|token| match token {
+                Token::NearMiss(miss) => Some((
+                    miss.offset,
+                    String::from_utf8_lossy(miss.fragment).to_string(),
+                    miss.reason.to_string(),
+                )),
+                Token::Call(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn near_misses_say_what_is_wrong() {
+        let misses = near_misses("mul(4*3] mul ( 2 , 4 ) mul(1234,5) do(1) mul(2,3,4)\nmul(7,");
+        let misses: Vec<(usize, &str, &str)> = misses
+            .iter()
+            .map(|(offset, fragment, reason)| (*offset, fragment.as_str(), reason.as_str()))
+            .collect();
+        assert_eq!(
+            misses,
+            vec![
+                (0, "mul(4*", "expected `,` after argument 1, found `*`"),
+                (9, "mul ( 2 , 4 )", "space between the name and `(`"),
+                (23, "mul(1234,5)", "argument 1 has 4 digits, at most 3 allowed"),
+                (35, "do(1)", "expected `)`, found `1`"),
+                (41, "mul(2,3,4)", "expected `)`, found `,`"),
+                (52, "mul(7,", "expected a digit for argument 2, found the end of memory"),
+            ]
+        );
+    }
+
+    #[test]
+    fn words_that_only_share_a_name_are_no_near_misses() {
+        assert!(near_misses("don't() do_not_mul(5,5) mul[3,7] undo()").is_empty());
+    }
+}
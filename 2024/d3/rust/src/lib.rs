@@ -0,0 +1,54 @@
+use aoc_common::{Answer, ParseError, Solution};
+
+pub mod interpreter;
+pub mod listing;
+
+use interpreter::{Instruction, Registry};
+
+/// Part 1 knows only `mul`, so every multiplication counts.
+fn first_task(memory: &str) -> i64 {
+    Registry::new().with(Instruction::MUL).run(memory.as_bytes()).sum
+}
+
+fn second_task(memory: &str) -> i64 {
+    Registry::builtin().run(memory.as_bytes()).sum
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    /// The whole memory, line breaks included.
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(memory: &Self::Input) -> Answer {
+        first_task(memory).into()
+    }
+
+    fn part2(memory: &Self::Input) -> Answer {
+        second_task(memory).into()
+    }
+}
+
+aoc_common::example_tests!(Day3: example1, example2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_carries_across_lines() {
+        let memory = "mul(2,3)don't()\nmul(4,4)\ndo()mul(1,5)\n";
+        assert_eq!(first_task(memory), 6 + 16 + 5);
+        assert_eq!(second_task(memory), 6 + 5);
+    }
+
+    #[test]
+    fn sums_do_not_overflow_i32() {
+        let memory = "mul(999,999)".repeat(3000);
+        assert_eq!(first_task(&memory), 999 * 999 * 3000);
+    }
+}
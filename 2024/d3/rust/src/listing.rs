@@ -0,0 +1,193 @@
+//! A disassembly of the memory: every call the scanner reads, where it is
+//! and what it does to the state, and the near misses it turned down.
+
+use std::fmt;
+
+use crate::interpreter::{Call, NearMiss, Registry, State, Token};
+
+/// A call and the state around it.
+#[derive(Debug, Clone)]
+pub struct Step<'a> {
+    pub call: Call<'a>,
+    /// 1-based, like the line of the input.
+    pub line: usize,
+    /// 1-based, in bytes.
+    pub column: usize,
+    /// Whether `mul` counted when the call ran, before it changed anything.
+    pub enabled: bool,
+    /// The sum once the call ran.
+    pub sum: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rejection<'a> {
+    pub near_miss: NearMiss<'a>,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Listing<'a> {
+    pub steps: Vec<Step<'a>>,
+    pub rejections: Vec<Rejection<'a>>,
+    pub state: State,
+    /// Whether printing includes the rejected near misses.
+    pub near_misses: bool,
+}
+
+/// Line and column of every offset, from where the lines start.
+struct Lines(Vec<usize>);
+
+impl Lines {
+    fn of(memory: &[u8]) -> Self {
+        let breaks = memory.iter().enumerate().filter(|&(_, &byte)| byte == b'\n');
+        Lines(std::iter::once(0).chain(breaks.map(|(index, _)| index + 1)).collect())
+    }
+
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.0.partition_point(|&start| start <= offset);
+        (line, offset - self.0[line - 1] + 1)
+    }
+}
+
+/// Runs `memory` with the instructions of `registry`, noting every step.
+pub fn disassemble<'a>(registry: &'a Registry, memory: &'a [u8]) -> Listing<'a> {
+    let lines = Lines::of(memory);
+    let mut state = State::default();
+    let mut steps = Vec::new();
+    let mut rejections = Vec::new();
+
+    for token in registry.tokens(memory) {
+        match token {
+            Token::Call(call) => {
+                let (line, column) = lines.position(call.offset);
+                let enabled = state.enabled;
+                (call.instruction.handler)(&mut state, &call.args);
+                steps.push(Step {
+                    call,
+                    line,
+                    column,
+                    enabled,
+                    sum: state.sum,
+                });
+            }
+            Token::NearMiss(near_miss) => {
+                let (line, column) = lines.position(near_miss.offset);
+                rejections.push(Rejection {
+                    near_miss,
+                    line,
+                    column,
+                });
+            }
+        }
+    }
+
+    Listing {
+        steps,
+        rejections,
+        state,
+        near_misses: false,
+    }
+}
+
+impl Listing<'_> {
+    pub fn with_near_misses(self, near_misses: bool) -> Self {
+        Listing { near_misses, ..self }
+    }
+}
+
+impl fmt::Display for Listing<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>8}  {:>10}  {:<16} {:<8} {:>12}", "offset", "line:col", "instruction", "enabled", "sum")?;
+        for step in self.steps.iter() {
+            writeln!(
+                f,
+                "{:>8}  {:>10}  {:<16} {:<8} {:>12}",
+                step.call.offset,
+                format!("{}:{}", step.line, step.column),
+                step.call.to_string(),
+                if step.enabled { "yes" } else { "no" },
+                step.sum
+            )?;
+        }
+        writeln!(
+            f,
+            "{} calls, sum {}, {}",
+            self.steps.len(),
+            self.state.sum,
+            if self.state.enabled { "enabled" } else { "disabled" }
+        )?;
+
+        if self.near_misses {
+            writeln!(f, "\nrejected near misses:")?;
+            for rejection in self.rejections.iter() {
+                writeln!(
+                    f,
+                    "{:>8}  {:>10}  {:<24} {}",
+                    rejection.near_miss.offset,
+                    format!("{}:{}", rejection.line, rejection.column),
+                    rejection.near_miss.fragment.escape_ascii().to_string(),
+                    rejection.near_miss.reason
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn steps_carry_position_enabled_and_running_sum() {
+        let registry = Registry::builtin();
+        let memory = format!("{}\nmul(1234,5)mul(3,3)", EXAMPLE);
+        let listing = disassemble(&registry, memory.as_bytes());
+
+        let steps: Vec<_> = listing
+            .steps
+            .iter()
+            .map(|step| {
+                let call = &step.call;
+                (call.instruction.name, call.args.as_slice(), step.line, step.column, step.enabled, step.sum)
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("mul", &[2, 4][..], 1, 2, true, 8),
+                ("don't", &[], 1, 21, true, 8),
+                ("mul", &[5, 5], 1, 29, false, 8),
+                ("mul", &[11, 8], 1, 49, false, 8),
+                ("do", &[], 1, 60, false, 8),
+                ("mul", &[8, 5], 1, 65, true, 48),
+                ("mul", &[3, 3], 2, 12, true, 57),
+            ]
+        );
+        assert_eq!(listing.state, registry.run(memory.as_bytes()));
+
+        let rejections: Vec<(usize, usize)> = listing
+            .rejections
+            .iter()
+            .map(|rejection| (rejection.line, rejection.column))
+            .collect();
+        assert_eq!(rejections, vec![(1, 38), (2, 1)]);
+    }
+
+    #[test]
+    fn near_misses_are_printed_only_when_asked() {
+        let registry = Registry::builtin();
+        let listing = disassemble(&registry, b"mul(2,4) mul(1234,5)");
+        let printed = listing.clone().to_string();
+        assert!(printed.contains("       0         1:1  mul(2,4)         yes                 8\n"), "{}", printed);
+        assert!(printed.ends_with("1 calls, sum 8, enabled\n"));
+
+        let printed = listing.with_near_misses(true).to_string();
+        assert!(printed.ends_with(
+            "rejected near misses:\n       9        1:10  mul(1234,5)              argument 1 has 4 digits, at most 3 allowed\n"
+        ), "{}", printed);
+    }
+}
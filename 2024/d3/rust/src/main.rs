@@ -1,82 +1,46 @@
-use regex::Regex;
-use std::fs::File;
-use std::io::{BufReader, BufRead};
+use aoc_common::{InputProvider, Solution};
+use clap::Parser;
+use day3::interpreter::{Instruction, Registry};
+use day3::Day3;
+use tracing::info;
 use std::error::Error;
 
-#[derive(Debug, Clone, Copy)]
-struct State {
-    sum: i32,
-    filter: bool,
+#[derive(Parser)]
+struct Args {
+    /// Also list every call with its position, the enabled flag and the
+    /// running sum, for part 1 or 2
+    #[arg(long, value_name = "PART", num_args = 0..=1, default_missing_value = "2",
+          value_parser = clap::value_parser!(u8).range(1..=2))]
+    disassemble: Option<u8>,
+    /// Add the near misses the scanner rejected, and why
+    #[arg(long, requires = "disassemble")]
+    near_misses: bool,
 }
 
-impl State {
-    fn empty() -> Self {
-        State { sum: 0, filter: true }
-    }
-}
-
-fn read_lines(file_path: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines().collect::<Vec<_>>();
-    lines.into_iter().collect::<Result<Vec<_>, _>>().map_err(From::from)
-}
-
-fn process_command(state: State, command: &str) -> State {
-    let mul_pattern = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    match command {
-        "do()" => State { filter: true, ..state },
-        "don't()" => State { filter: false, ..state },
-        _ => {
-            if state.filter {
-                if let Some(caps) = mul_pattern.captures(command) {
-                    let first: i32 = caps[1].parse().unwrap();
-                    let second: i32 = caps[2].parse().unwrap();
-                    State { sum: state.sum + first * second, ..state }
-                } else {
-                    state
-                }
-            } else {
-                state
-            }
-        }
-    }
-}
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    aoc_common::init_logger();
 
-fn first_task(lines: Vec<String>) -> i32 {
-    let mul_pattern = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    lines.iter()
-        .flat_map(|line| {
-            mul_pattern.find_iter(line).map(|mat| {
-                let caps = mul_pattern.captures(mat.as_str()).unwrap();
-                let first: i32 = caps[1].parse().unwrap();
-                let second: i32 = caps[2].parse().unwrap();
-                first * second
-            })
-        })
-        .sum()
-}
+    let input = InputProvider::from_env().fetch(2024, 3)?;
+    info!("Read source data from {}", input.path.display());
 
-fn second_task(lines: Vec<String>) -> i32 {
-    let filter_mul_pattern = Regex::new(r"(do\(\)|mul\(\d{1,3},\d{1,3}\)|don't\(\))").unwrap();
-    let mut state = State::empty();
-    for line in lines {
-        for mat in filter_mul_pattern.find_iter(&line) {
-            state = process_command(state, mat.as_str());
-        }
-    }
-    state.sum
-}
+    let memory = Day3::parse(&input.text).map_err(|e| e.with_file(&input.path))?;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let source_file_name = "./d3/input.txt";
-    let lines = read_lines(source_file_name).unwrap();
+    let first_result = Day3::part1(&memory);
+    aoc_common::print_result(1, "Uncorrupted mul result", first_result);
 
-    let first_result = first_task(lines.clone());
-    println!("1st Task: Uncorrupted mul result: {}", first_result);
+    let second_result = Day3::part2(&memory);
+    aoc_common::print_result(2, "Filtered Uncorrupted mul result", second_result);
 
-    let second_result = second_task(lines);
-    println!("2nd Task: Filtered Uncorrupted mul result: {}", second_result);
+    if let Some(part) = args.disassemble {
+        let registry = if part == 1 {
+            Registry::new().with(Instruction::MUL)
+        } else {
+            Registry::builtin()
+        };
+        let listing = day3::listing::disassemble(&registry, memory.as_bytes());
+        print!("{}", listing.with_near_misses(args.near_misses));
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,81 @@
+use aoc_common::point::{DIAGONAL, NEIGHBOURS};
+use aoc_common::{Answer, Grid, ParseError, Point, Solution};
+
+pub type Matrix = Grid<char>;
+
+fn find_pattern(matrix: &Matrix, pattern: &str, position: Point<i64>, shift: Point<i64>) -> i32 {
+    if !pattern.is_empty() {
+        let next_position = position + shift;
+        if let Some(&ch) = matrix.at(next_position) {
+            if ch == pattern.chars().next().unwrap() {
+                return find_pattern(matrix, &pattern[1..], next_position, shift);
+            }
+        }
+        0
+    } else {
+        1
+    }
+}
+
+fn first_task(matrix: &Matrix) -> i32 {
+    let pattern = "XMAS";
+    let first_char = pattern.chars().next().unwrap();
+    let mut count = 0;
+
+    for ((x, y), &char) in matrix.cells() {
+        if char == first_char {
+            let current_position = Point::new(x, y).signed();
+            count += NEIGHBOURS
+                .map(|shift| find_pattern(matrix, &pattern[1..], current_position, shift))
+                .iter()
+                .sum::<i32>();
+        }
+    }
+    count
+}
+
+fn second_task(matrix: &Matrix) -> i32 {
+    let mut count = 0;
+
+    for ((x, y), &c) in matrix.cells() {
+        if c == 'A' {
+            let a_position = Point::new(x, y).signed();
+            count += DIAGONAL
+                .iter()
+                .flat_map(|&shift| {
+                    matrix
+                        .at(a_position + shift)
+                        .filter(|&&x| x == 'M')
+                        .into_iter()
+                        .flat_map(|_| matrix.at(a_position - shift))
+                        .filter(|&&x| x == 'S')
+                        .map(|_| 0.5)
+                        .collect::<Vec<_>>()
+                })
+                .sum::<f32>() as i32;
+        }
+    }
+
+    count
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Matrix;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::from_chars(input)
+    }
+
+    fn part1(matrix: &Self::Input) -> Answer {
+        first_task(matrix).into()
+    }
+
+    fn part2(matrix: &Self::Input) -> Answer {
+        second_task(matrix).into()
+    }
+}
+
+aoc_common::example_tests!(Day4: example1);
+
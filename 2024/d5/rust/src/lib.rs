@@ -0,0 +1,130 @@
+use aoc_common::parse::{numbered_lines, parse_token};
+use aoc_common::{Answer, Params, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+pub type OrderRules = HashMap<usize, HashSet<usize>>;
+pub type Pages = Vec<Vec<usize>>;
+
+fn read_lists(input: &str, strict: bool) -> Result<(OrderRules, Pages), ParseError> {
+    let mut lines = numbered_lines(input);
+
+    let mut orders = HashMap::new();
+    let mut pages = Vec::new();
+
+    // Read orders
+    for (line_number, line) in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        let parts: Vec<usize> = line
+            .split('|')
+            .map(|s| parse_token(line_number, line, s.trim(), "page number"))
+            .collect::<Result<_, _>>()?;
+        if let [before, after] = parts[..] {
+            orders
+                .entry(before)
+                .or_insert_with(HashSet::new)
+                .insert(after);
+        } else if strict {
+            return Err(ParseError::expected(line_number, 1, "an ordering rule X|Y", line));
+        }
+    }
+
+    // Read pages
+    for (line_number, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let page: Vec<usize> = line
+            .split(',')
+            .map(|s| parse_token(line_number, line, s.trim(), "page number"))
+            .collect::<Result<_, _>>()?;
+        pages.push(page);
+    }
+
+    Ok((orders, pages))
+}
+
+fn identify_correct_updates(order_rules: &OrderRules, pages: &[usize]) -> (bool, Vec<usize>) {
+    let is_broken = pages.iter().any(|&page| {
+        let empty_set = HashSet::new();
+        let after_page = order_rules.get(&page).unwrap_or(&empty_set);
+        let before = &pages[..pages.iter().position(|&p| p == page).unwrap()];
+        before.iter().any(|&b| after_page.contains(&b))
+    });
+    (is_broken, pages.to_vec())
+}
+
+fn first_task(order_rules: &OrderRules, updates: &[Vec<usize>]) -> usize {
+    updates
+        .iter()
+        .map(|pages| identify_correct_updates(order_rules, pages))
+        .filter(|(is_broken, _)| !is_broken)
+        .flat_map(|(_, pages)| pages.get(pages.len() / 2).cloned())
+        .sum()
+}
+
+fn second_task(order_rules: &OrderRules, updates: &[Vec<usize>]) -> usize {
+    updates
+        .iter()
+        .map(|pages| identify_correct_updates(order_rules, pages))
+        .filter(|(is_broken, _)| *is_broken)
+        .flat_map(|(_, pages)| {
+            let mut sorted_pages = pages.clone();
+            sorted_pages.sort_by(|a, b| {
+                if order_rules.get(a).is_some_and(|set| set.contains(b)) {
+                    std::cmp::Ordering::Less
+                } else {
+                    std::cmp::Ordering::Greater
+                }
+            });
+            let half = sorted_pages.len() / 2;
+            sorted_pages.get(half).cloned()
+        })
+        .sum()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (OrderRules, Pages);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_lists(input, false)
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        read_lists(input, params.is_strict())
+    }
+
+    fn part1((orders, pages): &Self::Input) -> Answer {
+        first_task(orders, pages).into()
+    }
+
+    fn part2((orders, pages): &Self::Input) -> Answer {
+        second_task(orders, pages).into()
+    }
+}
+
+aoc_common::example_tests!(Day5: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reports_position_of_bad_page_number() {
+        let error = Day5::parse("47|53\n97|13\n\n75,4x,53\n").unwrap_err();
+        assert_eq!(error.to_string(), "4:4: expected page number, found `4x`");
+    }
+
+    #[test]
+    fn strict_parse_rejects_malformed_rules() {
+        let input = "47|53|61\n\n75,47,53\n";
+        assert!(Day5::parse(input).is_ok());
+
+        let error = Day5::parse_with(input, &Params::new().strict(true)).unwrap_err();
+        assert_eq!(error.line(), Some(1));
+        assert_eq!(error.expected_token(), Some("an ordering rule X|Y"));
+    }
+}
@@ -0,0 +1,136 @@
+use aoc_common::parse::{column, numbered_lines, parse_token};
+use aoc_common::{Answer, Params, ParseError, Solution};
+
+pub type Eqs = Vec<(i64, Vec<i64>)>;
+
+fn read_lists(input: &str, strict: bool) -> Result<Eqs, ParseError> {
+    let mut eqs = Vec::new();
+
+    for (line_number, line) in numbered_lines(input) {
+        if !line.is_empty() {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() == 2 {
+                let head = parse_token(line_number, line, parts[0].trim(), "test value")?;
+                let numbers: Vec<i64> = if strict {
+                    parts[1]
+                        .split_whitespace()
+                        .map(|s| parse_token(line_number, line, s, "number"))
+                        .collect::<Result<_, _>>()?
+                } else {
+                    parts[1]
+                        .split_whitespace()
+                        .filter_map(|s| s.parse::<i64>().ok())
+                        .collect()
+                };
+                if numbers.is_empty() {
+                    let end = &line[line.len()..];
+                    return Err(ParseError::expected(line_number, column(line, end), "number", ""));
+                }
+                eqs.push((head, numbers));
+            } else if strict {
+                return Err(ParseError::expected(line_number, 1, "an equation `value: numbers`", line));
+            }
+        }
+    }
+    Ok(eqs)
+}
+
+fn concat(x: i64, y: i64) -> i64 {
+    format!("{}{}", x, y).parse().unwrap()
+}
+
+fn calc1(agg: i64, ing: &[i64], result: i64) -> bool {
+    match ing {
+        [] => agg == result,
+        [head] => agg * head == result || agg + head == result ,
+        [head, tail @ ..] => {
+            calc1(head * agg, tail, result) || calc1(head + agg, tail, result)
+        }
+    }
+}
+
+fn calc2(agg: i64, ing: &[i64], result: i64) -> bool {
+    match ing {
+        [] => agg == result,
+        [head] => agg * head == result || agg + head == result || concat(agg, *head) == result,
+        [head, tail @ ..] => {
+            calc2(head * agg, tail, result)
+                || calc2(head + agg, tail, result)
+                || calc2(concat(agg, *head), tail, result)
+        }
+    }
+}
+
+fn first_task(eqs: &Eqs) -> i64 {
+    eqs.iter()
+        .filter_map(|&(result, ref numbers)| {
+            if calc1(numbers[0], &numbers[1..], result) {
+                Some(result)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+fn second_task(eqs: &Eqs) -> i128 {
+    eqs.iter()
+        .filter_map(|&(result, ref numbers)| {
+            if calc2(numbers[0], &numbers[1..], result) {
+                Some(result as i128)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Eqs;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_lists(input, false)
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        read_lists(input, params.is_strict())
+    }
+
+    fn part1(eqs: &Self::Input) -> Answer {
+        first_task(eqs).into()
+    }
+
+    fn part2(eqs: &Self::Input) -> Answer {
+        second_task(eqs).into()
+    }
+}
+
+aoc_common::example_tests!(Day7: example1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reports_bad_test_value() {
+        let error = Day7::parse("190: 10 19\n3x67: 17 5\n").unwrap_err();
+        assert_eq!(error.to_string(), "2:1: expected test value, found `3x67`");
+    }
+
+    #[test]
+    fn parse_rejects_equation_without_numbers() {
+        let error = Day7::parse("190: 10 19\n83:\n").unwrap_err();
+        assert_eq!(error.to_string(), "2:4: expected number, found nothing");
+    }
+
+    #[test]
+    fn strict_parse_rejects_skipped_numbers() {
+        let input = "190: 10 1x9\n";
+        assert_eq!(Day7::parse(input).unwrap(), vec![(190, vec![10])]);
+
+        let error = Day7::parse_with(input, &Params::new().strict(true)).unwrap_err();
+        assert_eq!(error.to_string(), "1:9: expected number, found `1x9`");
+    }
+}
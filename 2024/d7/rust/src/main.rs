@@ -1,96 +1,21 @@
-use std::fs;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::str::FromStr;
+use aoc_common::{InputProvider, Solution};
+use day7::Day7;
+use tracing::info;
+use std::error::Error;
 
-type EQS = Vec<(i64, Vec<i64>)>;
+fn main() -> Result<(), Box<dyn Error>> {
+    aoc_common::init_logger();
 
-fn read_lists<P>(filename: P) -> io::Result<EQS>
-where
-    P: AsRef<Path>,
-{
-    let file = fs::File::open(filename)?;
-    let reader = io::BufReader::new(file);
-    let mut eqs = Vec::new();
+    let input = InputProvider::from_env().fetch(2024, 7)?;
+    info!("Read source data from {}", input.path.display());
 
-    for line in reader.lines() {
-        let line = line?;
-        if !line.is_empty() {
-            let parts: Vec<&str> = line.split(':').collect();
-            if parts.len() == 2 {
-                let head = i64::from_str(parts[0].trim()).unwrap();
-                let numbers: Vec<i64> = parts[1]
-                    .trim()
-                    .split_whitespace()
-                    .filter_map(|s| s.parse::<i64>().ok())
-                    .collect();
-                eqs.push((head, numbers));
-            }
-        }
-    }
-    Ok(eqs)
-}
-
-fn concat(x: i64, y: i64) -> i64 {
-    format!("{}{}", x, y).parse().unwrap()
-}
+    let eqs = Day7::parse(&input.text).map_err(|e| e.with_file(&input.path))?;
 
-fn calc1(agg: i64, ing: &[i64], result: i64) -> bool {
-    match ing {
-        [] => agg == result,
-        [head] => agg * head == result || agg + head == result ,
-        [head, tail @ ..] => {
-            calc1(head * agg, tail, result) || calc1(head + agg, tail, result)
-        }
-    }
-}
+    let first_result = Day7::part1(&eqs);
+    aoc_common::print_result(1, "", first_result);
 
-fn calc2(agg: i64, ing: &[i64], result: i64) -> bool {
-    match ing {
-        [] => agg == result,
-        [head] => agg * head == result || agg + head == result || concat(agg, *head) == result,
-        [head, tail @ ..] => {
-            calc2(head * agg, tail, result)
-                || calc2(head + agg, tail, result)
-                || calc2(concat(agg, *head), tail, result)
-        }
-    }
-}
-
-fn first_task(eqs: &EQS) -> i64 {
-    eqs.iter()
-        .filter_map(|&(result, ref numbers)| {
-            if calc1(numbers[0], &numbers[1..], result) {
-                Some(result)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
-
-fn second_task(eqs: &EQS) -> i128 {
-    eqs.iter()
-        .filter_map(|&(result, ref numbers)| {
-            if calc2(numbers[0], &numbers[1..], result) {
-                Some(result as i128)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
-
-fn main() -> io::Result<()> {
-    let source_file_name = "./d7/input.txt";
-    println!("Read source data from {}", source_file_name);
-    let eqs = read_lists(source_file_name)?;
-
-    let first_result = first_task(&eqs);
-    println!("1st Task: {}", first_result);
-
-    let second_result = second_task(&eqs);
-    println!("2nd Task: {}", second_result);
+    let second_result = Day7::part2(&eqs);
+    aoc_common::print_result(2, "", second_result);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,487 @@
+//! Timing of the parse, part 1 and part 2 phases of a day.
+//!
+//! Every run is appended to a JSON history (`YEAR/bench.json` by default),
+//! and each phase is compared with the last run on the same input so a
+//! change that makes a day slower is flagged.
+
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use aoc_common::{alloc, input_hash, AllocStats, Params, Parsed, Part, Puzzle};
+use serde::{Deserialize, Serialize};
+
+use crate::run::{self, PuzzleInput, RunError, Slot, Status};
+
+/// Only this many runs are kept in the history.
+const KEPT_RUNS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub fn of(part: Part) -> Phase {
+        match part {
+            Part::One => Phase::Part1,
+            Part::Two => Phase::Part2,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part 1",
+            Phase::Part2 => "part 2",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub warmup: usize,
+    pub iterations: usize,
+    /// How long one round of a part may take before it counts as TIMEOUT.
+    pub deadline: Option<Duration>,
+}
+
+/// Summary of one phase's samples, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub year: u32,
+    pub day: u32,
+    pub phase: Phase,
+    /// `input_hash` of the input, so runs on other inputs aren't compared.
+    pub input: String,
+    pub iterations: usize,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub min_ns: u64,
+    /// What the last round allocated, when allocations are counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    /// The phase ran past its deadline; the times are then the deadline.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub timeout: bool,
+}
+
+impl Timing {
+    fn new(
+        puzzle: &Puzzle,
+        phase: Phase,
+        input: &str,
+        mut samples: Vec<Duration>,
+        alloc: Option<AllocStats>,
+    ) -> Timing {
+        samples.sort();
+        let nanos = |duration: Duration| duration.as_nanos() as u64;
+        Timing {
+            year: puzzle.year,
+            day: puzzle.day,
+            phase,
+            input: input.to_string(),
+            iterations: samples.len(),
+            median_ns: nanos(median(&samples)),
+            p95_ns: nanos(percentile(&samples, 95)),
+            min_ns: nanos(samples[0]),
+            alloc,
+            timeout: false,
+        }
+    }
+
+    fn timed_out(puzzle: &Puzzle, phase: Phase, input: &str, deadline: Duration) -> Timing {
+        let deadline = deadline.as_nanos() as u64;
+        Timing {
+            year: puzzle.year,
+            day: puzzle.day,
+            phase,
+            input: input.to_string(),
+            iterations: 0,
+            median_ns: deadline,
+            p95_ns: deadline,
+            min_ns: deadline,
+            alloc: None,
+            timeout: true,
+        }
+    }
+
+    fn is_same_benchmark(&self, other: &Timing) -> bool {
+        (self.year, self.day, self.phase, &self.input) == (other.year, other.day, other.phase, &other.input)
+    }
+}
+
+fn median(sorted: &[Duration]) -> Duration {
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2
+    } else {
+        sorted[middle]
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Parses the input and solves the parts `warmup + iterations` times,
+/// keeping the samples of the last `iterations` rounds. The allocations are
+/// those of the last round, as every round does the same work. A part that
+/// runs past the deadline isn't run again and gets a TIMEOUT timing.
+pub fn measure(
+    puzzle: &Puzzle,
+    input: &PuzzleInput,
+    params: &Params,
+    parts: &[Part],
+    settings: Settings,
+) -> Result<Vec<Timing>, RunError> {
+    let mut parse = Vec::with_capacity(settings.iterations);
+    let mut parse_alloc = None;
+    let mut solve = vec![(Vec::with_capacity(settings.iterations), None, false); parts.len()];
+    let slot: Slot = Arc::new(());
+
+    for round in 0..settings.warmup + settings.iterations {
+        let recorded = round >= settings.warmup;
+        let started = Instant::now();
+        let (parsed, alloc) = alloc::measure(|| run::parse(puzzle, black_box(input), params));
+        let parsed: Arc<dyn Parsed> = Arc::from(parsed?);
+        if recorded {
+            parse.push(started.elapsed());
+            parse_alloc = alloc;
+        }
+        for ((samples, last_alloc, timed_out), &part) in solve.iter_mut().zip(parts) {
+            if *timed_out {
+                continue;
+            }
+            let started = Instant::now();
+            match run::solve_parsed(puzzle, &parsed, part, settings.deadline, &slot) {
+                (Status::Solved(answer), alloc) => {
+                    black_box(answer);
+                    if recorded {
+                        samples.push(started.elapsed());
+                        *last_alloc = alloc;
+                    }
+                }
+                (Status::Timeout, _) => *timed_out = true,
+            }
+        }
+    }
+
+    let hash = input_hash(&input.text);
+    let mut timings = vec![Timing::new(puzzle, Phase::Parse, &hash, parse, parse_alloc)];
+    for ((samples, alloc, timed_out), &part) in solve.into_iter().zip(parts) {
+        timings.push(match settings.deadline {
+            Some(deadline) if timed_out => Timing::timed_out(puzzle, Phase::of(part), &hash, deadline),
+            _ => Timing::new(puzzle, Phase::of(part), &hash, samples, alloc),
+        });
+    }
+    Ok(timings)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub timings: Vec<Timing>,
+}
+
+impl Run {
+    pub fn now(timings: Vec<Timing>) -> Run {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        Run { timestamp, timings }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<History, RunError> {
+        let input_error = |source| RunError::Input {
+            path: path.display().to_string(),
+            source,
+        };
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| input_error(io::Error::new(io::ErrorKind::InvalidData, e))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(input_error(e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RunError> {
+        let output_error = |source| RunError::Output {
+            path: path.display().to_string(),
+            source,
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(output_error)?;
+        }
+        let json = serde_json::to_string_pretty(self).expect("history serializes");
+        fs::write(path, json + "\n").map_err(output_error)
+    }
+
+    /// The most recent timing of the same phase of the same day and input.
+    pub fn previous(&self, timing: &Timing) -> Option<&Timing> {
+        self.runs
+            .iter()
+            .rev()
+            .flat_map(|run| run.timings.iter())
+            .find(|previous| previous.is_same_benchmark(timing))
+    }
+
+    pub fn push(&mut self, run: Run) {
+        self.runs.push(run);
+        let excess = self.runs.len().saturating_sub(KEPT_RUNS);
+        self.runs.drain(..excess);
+    }
+}
+
+/// A timing next to the previous one, for printing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<'a> {
+    pub timing: &'a Timing,
+    pub previous: Option<&'a Timing>,
+    /// Slowdown in percent above which the median counts as a regression.
+    pub threshold: f64,
+}
+
+impl Comparison<'_> {
+    /// Change of the median since the previous run, in percent. Runs that
+    /// timed out have no median to compare.
+    pub fn change(&self) -> Option<f64> {
+        let previous = self.previous?;
+        if self.timing.timeout || previous.timeout || previous.median_ns == 0 {
+            return None;
+        }
+        Some((self.timing.median_ns as f64 / previous.median_ns as f64 - 1.0) * 100.0)
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.change().is_some_and(|change| change > self.threshold)
+    }
+}
+
+impl fmt::Display for Comparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timing = self.timing;
+        if timing.timeout {
+            return write!(
+                f,
+                "{} day {:>2} {:<6}  TIMEOUT after {}",
+                timing.year,
+                timing.day,
+                timing.phase,
+                nanos(timing.median_ns)
+            );
+        }
+        write!(
+            f,
+            "{} day {:>2} {:<6}  median {:>10}  p95 {:>10}",
+            timing.year,
+            timing.day,
+            timing.phase,
+            nanos(timing.median_ns),
+            nanos(timing.p95_ns)
+        )?;
+        match (self.change(), self.previous) {
+            (Some(change), Some(previous)) if self.is_regression() => write!(
+                f,
+                "  REGRESSION {:+.1}% (was {})",
+                change,
+                nanos(previous.median_ns)
+            ),
+            (Some(change), _) => write!(f, "  {:+.1}%", change),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Formats nanoseconds with a unit that keeps three or four digits.
+pub fn nanos(nanos: u64) -> String {
+    match nanos {
+        0..=9_999 => format!("{}ns", nanos),
+        10_000..=9_999_999 => format!("{:.1}µs", nanos as f64 / 1e3),
+        10_000_000..=9_999_999_999 => format!("{:.1}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use aoc_common::{cancel, Answer, ParseError, Solution};
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    fn timing(phase: Phase, input: &str, median_ns: u64) -> Timing {
+        Timing {
+            year: 2024,
+            day: 1,
+            phase,
+            input: input.to_string(),
+            iterations: 10,
+            median_ns,
+            p95_ns: median_ns,
+            min_ns: median_ns,
+            alloc: None,
+            timeout: false,
+        }
+    }
+
+    #[test]
+    fn median_and_p95_of_sorted_samples() {
+        let samples = millis(&(1..=20).collect::<Vec<_>>());
+        assert_eq!(median(&samples), Duration::from_micros(10_500));
+        assert_eq!(percentile(&samples, 95), Duration::from_millis(19));
+        assert_eq!(median(&millis(&[1, 2, 9])), Duration::from_millis(2));
+        assert_eq!(percentile(&millis(&[7]), 95), Duration::from_millis(7));
+    }
+
+    #[test]
+    fn measure_times_parse_and_requested_parts() {
+        let puzzle = registry::find(2024, 1).unwrap();
+        let input = PuzzleInput {
+            name: "sample.txt".to_string(),
+            text: "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n".to_string(),
+        };
+        let settings = Settings {
+            warmup: 1,
+            iterations: 5,
+            deadline: None,
+        };
+
+        let timings = measure(&puzzle, &input, &Params::new(), &[Part::Two], settings).unwrap();
+        let phases: Vec<Phase> = timings.iter().map(|timing| timing.phase).collect();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Part2]);
+        assert!(timings.iter().all(|timing| timing.iterations == 5));
+        assert_eq!(timings[0].input, input_hash(&input.text));
+        assert_eq!(timings[1].alloc.is_some(), alloc::is_counting());
+    }
+
+    /// Part 2 spins until it is cancelled.
+    struct Spin;
+
+    impl Solution for Spin {
+        type Input = ();
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Answer {
+            Answer::Number(1)
+        }
+
+        fn part2(_: &Self::Input) -> Answer {
+            while !cancel::cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn measure_records_parts_past_the_deadline_as_timeouts() {
+        let puzzle = Puzzle::new::<Spin>(2024, 99);
+        let input = PuzzleInput {
+            name: "sample.txt".to_string(),
+            text: String::new(),
+        };
+        let settings = Settings {
+            warmup: 1,
+            iterations: 3,
+            deadline: Some(Duration::from_millis(20)),
+        };
+
+        let timings = measure(&puzzle, &input, &Params::new(), &Part::ALL, settings).unwrap();
+        assert_eq!((timings[1].timeout, timings[1].iterations), (false, 3));
+        assert_eq!((timings[2].timeout, timings[2].iterations), (true, 0));
+        assert_eq!(timings[2].median_ns, 20_000_000);
+
+        let comparison = Comparison {
+            timing: &timings[2],
+            previous: Some(&timings[1]),
+            threshold: 10.0,
+        };
+        assert_eq!(comparison.to_string(), "2024 day 99 part 2  TIMEOUT after 20.0ms");
+        assert!(!comparison.is_regression());
+        assert!(serde_json::to_string(&timings[2]).unwrap().ends_with(",\"timeout\":true}"));
+        assert!(!serde_json::to_string(&timings[1]).unwrap().contains("timeout"));
+    }
+
+    #[test]
+    fn compares_with_latest_run_on_same_input() {
+        let mut history = History::default();
+        history.push(Run::now(vec![timing(Phase::Part1, "aaaa", 100)]));
+        history.push(Run::now(vec![timing(Phase::Part1, "bbbb", 500)]));
+        history.push(Run::now(vec![timing(Phase::Parse, "aaaa", 50)]));
+
+        let current = timing(Phase::Part1, "aaaa", 125);
+        let comparison = Comparison {
+            timing: &current,
+            previous: history.previous(&current),
+            threshold: 10.0,
+        };
+        assert_eq!(comparison.change(), Some(25.0));
+        assert!(comparison.is_regression());
+        assert!(comparison.to_string().ends_with("REGRESSION +25.0% (was 100ns)"), "{}", comparison);
+
+        let faster = timing(Phase::Parse, "aaaa", 45);
+        let comparison = Comparison {
+            timing: &faster,
+            previous: history.previous(&faster),
+            threshold: 10.0,
+        };
+        assert!(!comparison.is_regression());
+        assert!(comparison.to_string().ends_with("  -10.0%"), "{}", comparison);
+    }
+
+    #[test]
+    fn history_round_trips_through_json() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}/bench.json", std::process::id()));
+        assert_eq!(History::load(&path).unwrap(), History::default());
+
+        let mut history = History::default();
+        let mut counted = timing(Phase::Part1, "aaaa", 5_678);
+        counted.alloc = Some(AllocStats {
+            allocations: 3,
+            bytes: 4_096,
+            peak_bytes: 2_048,
+        });
+        history.push(Run {
+            timestamp: 1_733_000_000,
+            timings: vec![timing(Phase::Part2, "aaaa", 1_234), counted],
+        });
+        history.save(&path).unwrap();
+        assert_eq!(History::load(&path).unwrap(), history);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn nanos_picks_a_readable_unit() {
+        assert_eq!(nanos(950), "950ns");
+        assert_eq!(nanos(12_345), "12.3µs");
+        assert_eq!(nanos(45_600_000), "45.6ms");
+        assert_eq!(nanos(12_000_000_000), "12.00s");
+    }
+}
@@ -0,0 +1,213 @@
+//! Running every day of a year at once.
+//!
+//! Inputs are read with async file I/O, and the days are solved on tokio's
+//! blocking pool, at most `jobs` of them at a time. A day keeps its slot
+//! while a part that timed out is still winding down, so a part that never
+//! polls for cancellation holds it until the part ends. The results come
+//! back in the order the days were given, whichever finishes first.
+
+use std::io;
+use std::panic;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aoc_common::{InputProvider, Params, Part, Puzzle};
+use tokio::sync::Semaphore;
+use tokio::task::{self, JoinError};
+use tracing::{info_span, Instrument};
+
+use crate::bench::nanos;
+use crate::run::{self, DayResults, InputSource, PuzzleInput, RunError, Status};
+
+/// How one day of a whole-year run went.
+#[derive(Debug)]
+pub struct DayRun {
+    pub year: u32,
+    pub day: u32,
+    pub result: Result<DayResults, RunError>,
+}
+
+/// A panic in a day brings the run down, as it would have sequentially.
+fn resume(error: JoinError) -> ! {
+    panic::resume_unwind(error.into_panic())
+}
+
+/// Reads a day's cached input without blocking. A day that is not cached
+/// yet goes to the input provider on the blocking pool to be downloaded.
+pub async fn read_input(puzzle: Puzzle, inputs: InputProvider) -> Result<PuzzleInput, RunError> {
+    let path = inputs.cache_path(puzzle.year, puzzle.day);
+    let read = tokio::fs::read_to_string(&path)
+        .instrument(info_span!("input", year = puzzle.year, day = puzzle.day))
+        .await;
+    match read {
+        Ok(text) => Ok(PuzzleInput {
+            name: path.display().to_string(),
+            text,
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            task::spawn_blocking(move || InputSource::Default.read(&puzzle, &inputs))
+                .await
+                .unwrap_or_else(|e| resume(e))
+        }
+        Err(source) => Err(RunError::Input {
+            path: path.display().to_string(),
+            source,
+        }),
+    }
+}
+
+/// Solves the given parts of every puzzle, `jobs` days at a time.
+pub async fn run_all(
+    puzzles: &[Puzzle],
+    inputs: &InputProvider,
+    params: &Params,
+    parts: &[Part],
+    deadline: Option<Duration>,
+    jobs: usize,
+) -> Vec<DayRun> {
+    let slots = Arc::new(Semaphore::new(jobs));
+    let tasks: Vec<_> = puzzles
+        .iter()
+        .map(|&puzzle| {
+            let inputs = inputs.clone();
+            let params = params.clone();
+            let parts = parts.to_vec();
+            let slots = Arc::clone(&slots);
+            let span = info_span!("day", year = puzzle.year, day = puzzle.day);
+            task::spawn(async move {
+                let input = read_input(puzzle, inputs).instrument(span.clone()).await?;
+                let slot = slots.acquire_owned().await.expect("the semaphore is never closed");
+                task::spawn_blocking(move || {
+                    span.in_scope(|| run::solve_in_slot(&puzzle, &input, &params, &parts, deadline, Arc::new(slot)))
+                })
+                .await
+                .unwrap_or_else(|e| resume(e))
+            })
+        })
+        .collect();
+
+    let mut runs = Vec::with_capacity(tasks.len());
+    for (puzzle, task) in puzzles.iter().zip(tasks) {
+        runs.push(DayRun {
+            year: puzzle.year,
+            day: puzzle.day,
+            result: task.await.unwrap_or_else(|e| resume(e)),
+        });
+    }
+    runs
+}
+
+/// Whether every day was read and every part finished in time.
+pub fn all_solved(runs: &[DayRun]) -> bool {
+    runs.iter().all(|run| match &run.result {
+        Ok(results) => results.finished(),
+        Err(_) => false,
+    })
+}
+
+/// A table of every part's answer and time, one row per part in day order.
+/// Days that failed get a single `ERROR` row; the error itself is logged.
+pub fn summary(runs: &[DayRun]) -> String {
+    let mut rows = Vec::new();
+    for run in runs {
+        match &run.result {
+            Ok(results) => {
+                for part in results.parts.iter() {
+                    let answer = match &part.status {
+                        Status::Solved(answer) => answer.to_string(),
+                        Status::Timeout => "TIMEOUT".to_string(),
+                    };
+                    let time = nanos(part.elapsed.as_nanos() as u64);
+                    rows.push((run.year, run.day, part.part.to_string(), answer, time));
+                }
+            }
+            Err(_) => rows.push((run.year, run.day, "-".to_string(), "ERROR".to_string(), String::new())),
+        }
+    }
+
+    let width = rows.iter().map(|row| row.3.len()).max().unwrap_or(0).max("answer".len());
+    let mut table = format!("year  day  part  {:<width$}  {:>10}", "answer", "time");
+    for (year, day, part, answer, time) in rows {
+        let row = format!("{}  {:>3}  {:>4}  {:<width$}  {:>10}", year, day, part, answer, time);
+        table += "\n";
+        table += row.trim_end();
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use aoc_common::{Answer, ParseError, Solution};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    fn run_offline(cache: &std::path::Path, puzzles: &[Puzzle], deadline: Option<Duration>) -> Vec<DayRun> {
+        let inputs = InputProvider::default().cache_dir(cache).offline(true);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(run_all(puzzles, &inputs, &Params::new(), &Part::ALL, deadline, 1))
+    }
+
+    #[test]
+    fn days_come_back_in_order_with_their_errors() {
+        let cache = std::env::temp_dir().join(format!("aoc-concurrent-{}", std::process::id()));
+        std::fs::create_dir_all(cache.join("2024/d1")).unwrap();
+        std::fs::write(cache.join("2024/d1/input.txt"), "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        std::fs::create_dir_all(cache.join("2024/d2")).unwrap();
+        std::fs::write(cache.join("2024/d2/input.txt"), "7 6 4 2 1\n1 2 7 8 9\n").unwrap();
+        let puzzles: Vec<Puzzle> = [2, 5, 1].iter().map(|&day| registry::find(2024, day).unwrap()).collect();
+
+        let runs = run_offline(&cache, &puzzles, None);
+        let days: Vec<u32> = runs.iter().map(|run| run.day).collect();
+        assert_eq!(days, vec![2, 5, 1]);
+        assert!(matches!(runs[1].result, Err(RunError::Fetch(_))));
+        assert!(!all_solved(&runs));
+
+        let table = summary(&runs);
+        let rows: Vec<Vec<&str>> = table.lines().map(|line| line.split_whitespace().collect()).collect();
+        assert_eq!(rows[0], vec!["year", "day", "part", "answer", "time"]);
+        assert_eq!(rows[1][..4], ["2024", "2", "1", "1"]);
+        assert_eq!(rows[3], vec!["2024", "5", "-", "ERROR"]);
+        assert_eq!(rows[4][..4], ["2024", "1", "1", "11"]);
+        assert_eq!(rows[5][..4], ["2024", "1", "2", "31"]);
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static MOST_RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+    /// Keeps working well past any deadline without looking at its token.
+    struct Deaf;
+
+    impl Solution for Deaf {
+        type Input = ();
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Answer {
+            let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+            MOST_RUNNING.fetch_max(running, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(200));
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn timed_out_parts_keep_their_day_slot() {
+        let cache = std::env::temp_dir().join(format!("aoc-concurrent-slots-{}", std::process::id()));
+        for day in [1, 2] {
+            std::fs::create_dir_all(cache.join(format!("1999/d{}", day))).unwrap();
+            std::fs::write(cache.join(format!("1999/d{}/input.txt", day)), "").unwrap();
+        }
+        let puzzles = [Puzzle::new::<Deaf>(1999, 1), Puzzle::new::<Deaf>(1999, 2)];
+
+        let runs = run_offline(&cache, &puzzles, Some(Duration::from_millis(20)));
+        assert!(runs.iter().all(|run| !run.result.as_ref().unwrap().finished()));
+        assert_eq!(MOST_RUNNING.load(Ordering::SeqCst), 1);
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+}
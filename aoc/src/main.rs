@@ -0,0 +1,587 @@
+mod bench;
+mod concurrent;
+mod registry;
+mod report;
+mod run;
+mod scaffold;
+mod verify;
+mod watch;
+
+use std::fs;
+use std::path::PathBuf;
+use std::num::NonZeroUsize;
+use std::process::ExitCode;
+use std::slice;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Answer, InputProvider, KnownAnswers, Params, Part, Verdict};
+use clap::{Args, Parser, Subcommand};
+use report::Format;
+use run::{InputSource, RunError};
+use tracing::{error, info, info_span, warn};
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: aoc_common::alloc::CountingAllocator = aoc_common::alloc::CountingAllocator;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code solutions")]
+struct Cli {
+    /// Write a Chrome trace-event file of the spans and debug events
+    #[arg(long, global = true, value_name = "FILE", num_args = 0..=1, default_missing_value = "trace.json")]
+    trace: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every registered day of a year
+    Run(RunArgs),
+    /// Post a part's answer, solving it first when no answer is given
+    Submit(SubmitArgs),
+    /// Check every day of a year against the known answers in YEAR/answers.toml
+    Verify(VerifyArgs),
+    /// Time the parse and solve phases of one day, or every day of a year
+    Bench(BenchArgs),
+    /// Start a new day: its crate, example fixture and answers entry
+    New(NewArgs),
+    /// Run a day's example fixtures, and its real input with --input
+    Check(CheckArgs),
+    /// Check a day again whenever its sources or fixtures change
+    Watch(WatchArgs),
+    /// Save the examples of a cached puzzle page as test fixtures
+    Examples(ExamplesArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    year: u32,
+    /// Day to run; leave out together with --all to run the whole year
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+    /// Run every registered day of the year
+    #[arg(long)]
+    all: bool,
+    /// Run only this part (1 or 2)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input files or directories of them, or `-` for standard input
+    #[arg(long, value_name = "PATH", num_args = 1.., requires = "day")]
+    input: Vec<String>,
+    /// Puzzle parameter, such as `wide=11` for day 14
+    #[arg(long = "param", value_name = "NAME=VALUE", requires = "day")]
+    params: Vec<String>,
+    /// Reject input lines the parsers would otherwise skip
+    #[arg(long)]
+    strict: bool,
+    /// Seconds each part may run before it is reported as TIMEOUT; 0 waits forever
+    #[arg(long, value_name = "SECONDS", default_value_t = 60.0)]
+    timeout: f64,
+    /// How to print the results; json and csv give one record per part
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Days solved at once with --all; defaults to the number of CPUs
+    #[arg(long, short, value_name = "N", requires = "all",
+          value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    jobs: Option<usize>,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    year: u32,
+    day: u32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Answer to post instead of the solution's
+    answer: Option<String>,
+    /// Puzzle parameter used when solving, such as `wide=11` for day 14
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    year: u32,
+    /// Check only this day
+    day: Option<u32>,
+    /// Known answers to check against instead of YEAR/answers.toml
+    #[arg(long, value_name = "FILE")]
+    answers: Option<PathBuf>,
+    /// Seconds each part may run before it is reported as TIMEOUT; 0 waits forever
+    #[arg(long, value_name = "SECONDS", default_value_t = 60.0)]
+    timeout: f64,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    year: u32,
+    /// Day to time; leave out together with --all to time the whole year
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+    /// Time every registered day of the year
+    #[arg(long)]
+    all: bool,
+    /// Time only this part (1 or 2); parsing is always timed
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input file, or `-` for standard input
+    #[arg(long, requires = "day")]
+    input: Option<String>,
+    /// Puzzle parameter, such as `wide=11` for day 14
+    #[arg(long = "param", value_name = "NAME=VALUE", requires = "day")]
+    params: Vec<String>,
+    /// Untimed rounds before measuring
+    #[arg(long, default_value_t = 3)]
+    warmup: usize,
+    /// Timed rounds
+    #[arg(long, default_value_t = 20, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    iterations: usize,
+    /// Slowdown of the median, in percent, reported as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// Seconds a round of a part may run before it is reported as TIMEOUT; 0 waits forever
+    #[arg(long, value_name = "SECONDS", default_value_t = 60.0)]
+    timeout: f64,
+    /// Timing history to compare with and append to instead of YEAR/bench.json
+    #[arg(long, value_name = "FILE")]
+    history: Option<PathBuf>,
+    /// Compare with the history without adding this run to it
+    #[arg(long)]
+    no_save: bool,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Year and day, or only the day together with --year
+    #[arg(value_name = "[YEAR] DAY", num_args = 1..=2, required = true)]
+    numbers: Vec<u32>,
+    /// Year of the new day; defaults to the latest registered year
+    #[arg(long)]
+    year: Option<u32>,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    year: u32,
+    day: u32,
+    /// Print one JSON object per result instead of the summary
+    #[arg(long)]
+    json: bool,
+    #[command(flatten)]
+    settings: CheckSettings,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    year: u32,
+    day: u32,
+    #[command(flatten)]
+    settings: CheckSettings,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+#[derive(Args)]
+struct CheckSettings {
+    /// Also solve the real input and compare it with YEAR/answers.toml
+    #[arg(long)]
+    input: bool,
+    /// Seconds each part may run before it is reported as TIMEOUT; 0 waits forever
+    #[arg(long, value_name = "SECONDS", default_value_t = 10.0)]
+    timeout: f64,
+}
+
+#[derive(Args)]
+struct ExamplesArgs {
+    year: u32,
+    day: u32,
+    /// Replace fixtures that already exist
+    #[arg(long)]
+    force: bool,
+    /// Download the puzzle page again, to pick up part two
+    #[arg(long)]
+    refresh: bool,
+    #[command(flatten)]
+    inputs: InputArgs,
+}
+
+/// Where puzzle inputs are cached; `AOC_SESSION` is needed to download them.
+#[derive(Args)]
+struct InputArgs {
+    /// Only use cached inputs, never download [env: AOC_OFFLINE]
+    #[arg(long)]
+    offline: bool,
+    /// Directory holding the YEAR/dN/input.txt cache [env: AOC_CACHE_DIR]
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+}
+
+impl InputArgs {
+    fn provider(&self) -> InputProvider {
+        let mut inputs = InputProvider::from_env();
+        if self.offline {
+            inputs = inputs.offline(true);
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            inputs = inputs.cache_dir(cache_dir);
+        }
+        inputs
+    }
+}
+
+fn run(args: RunArgs) -> Result<bool, RunError> {
+    let parts = match args.part {
+        Some(part) => vec![Part::from_number(part as usize).expect("part is 1 or 2")],
+        None => Part::ALL.to_vec(),
+    };
+    let params = Params::from_pairs(&args.params)
+        .map_err(|e| RunError::Usage(e.to_string()))?
+        .strict(args.strict);
+    let inputs = args.inputs.provider();
+    let deadline = Duration::try_from_secs_f64(args.timeout).ok().filter(|timeout| !timeout.is_zero());
+
+    match args.day {
+        Some(day) => {
+            let puzzle = registry::find(args.year, day).ok_or(RunError::UnknownDay {
+                year: args.year,
+                day,
+            })?;
+            let _day = info_span!("day", year = puzzle.year, day = puzzle.day).entered();
+            let sources = InputSource::expand(&args.input)?;
+            if let [source] = sources.as_slice() {
+                let result = source
+                    .read(&puzzle, &inputs)
+                    .and_then(|input| run::solve_within(&puzzle, &input, &params, &parts, deadline));
+                if args.format == Format::Text {
+                    return Ok(run::print_results(&result?));
+                }
+                // a failed input still gets its error record before the failure
+                let run = run::InputRun {
+                    name: source.name(),
+                    result,
+                };
+                report::print(args.format, &report::input_records(puzzle.year, puzzle.day, slice::from_ref(&run)))?;
+                return run.result.map(|results| results.finished());
+            }
+
+            let runs = run::solve_inputs(&puzzle, &sources, &inputs, &params, &parts, deadline);
+            for input in runs.iter() {
+                if let Err(e) = &input.result {
+                    error!("{}: {}", input.name, e);
+                }
+            }
+            if args.format == Format::Text {
+                println!("{}", run::inputs_table(&runs, &parts));
+            } else {
+                report::print(args.format, &report::input_records(puzzle.year, puzzle.day, &runs))?;
+            }
+            Ok(runs.iter().all(|input| input.result.as_ref().is_ok_and(run::DayResults::finished)))
+        }
+        None => {
+            let puzzles = registry::year(args.year);
+            if puzzles.is_empty() {
+                return Err(RunError::UnknownYear(args.year));
+            }
+            let jobs = args
+                .jobs
+                .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get));
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .expect("can't start the tokio runtime");
+            let started = Instant::now();
+            let runs = runtime.block_on(concurrent::run_all(&puzzles, &inputs, &params, &parts, deadline, jobs));
+            info!("{} days in {:.1?} with {} jobs", runs.len(), started.elapsed(), jobs);
+
+            for day in runs.iter() {
+                if let Err(e) = &day.result {
+                    error!("{} day {}: {}", day.year, day.day, e);
+                }
+            }
+            if args.format == Format::Text {
+                println!("{}", concurrent::summary(&runs));
+                for results in runs.iter().filter_map(|day| day.result.as_ref().ok()) {
+                    run::print_allocations(results);
+                }
+            } else {
+                report::print(args.format, &report::year_records(&runs))?;
+            }
+            Ok(concurrent::all_solved(&runs))
+        }
+    }
+}
+
+fn submit(args: SubmitArgs) -> Result<bool, RunError> {
+    let part = Part::from_number(args.part as usize).expect("part is 1 or 2");
+    let inputs = args.inputs.provider();
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let puzzle = registry::find(args.year, args.day).ok_or(RunError::UnknownDay {
+                year: args.year,
+                day: args.day,
+            })?;
+            let params = Params::from_pairs(&args.params).map_err(|e| RunError::Usage(e.to_string()))?;
+            let input = InputSource::Default.read(&puzzle, &inputs)?;
+            match run::solve(&puzzle, &input, &params, &[part])?.remove(0).1 {
+                Answer::Unsolved => {
+                    return Err(RunError::Usage(format!(
+                        "{} day {} part {} has no answer to submit",
+                        args.year, args.day, part
+                    )))
+                }
+                answer => answer.to_string(),
+            }
+        }
+    };
+
+    let verdict = inputs.submit(args.year, args.day, part, &answer)?;
+    println!("{} day {} part {}: {} is {}", args.year, args.day, part, answer, verdict);
+    Ok(verdict == Verdict::Correct)
+}
+
+fn verify(args: VerifyArgs) -> Result<bool, RunError> {
+    let inputs = args.inputs.provider();
+    let path = args.answers.unwrap_or_else(|| aoc_common::answers_path(args.year));
+    let known = KnownAnswers::load(&path)?;
+    if known.is_empty() {
+        warn!("no known answers in {}, nothing to verify", path.display());
+    }
+    let puzzles = match args.day {
+        Some(day) => vec![registry::find(args.year, day).ok_or(RunError::UnknownDay { year: args.year, day })?],
+        None => registry::year(args.year),
+    };
+    if puzzles.is_empty() {
+        return Err(RunError::UnknownYear(args.year));
+    }
+
+    let deadline = Duration::try_from_secs_f64(args.timeout).ok().filter(|timeout| !timeout.is_zero());
+    let mut all_match = true;
+    for puzzle in puzzles.iter() {
+        match verify::verify(puzzle, &inputs, &known, deadline) {
+            Ok(checks) => {
+                for check in checks {
+                    all_match &= check.outcome.passed();
+                    println!("{}", check);
+                }
+            }
+            Err(e) => {
+                error!("{} day {}: {}", puzzle.year, puzzle.day, e);
+                all_match = false;
+            }
+        }
+    }
+    Ok(all_match)
+}
+
+fn bench(args: BenchArgs) -> Result<bool, RunError> {
+    let parts = match args.part {
+        Some(part) => vec![Part::from_number(part as usize).expect("part is 1 or 2")],
+        None => Part::ALL.to_vec(),
+    };
+    let params = Params::from_pairs(&args.params).map_err(|e| RunError::Usage(e.to_string()))?;
+    let settings = bench::Settings {
+        warmup: args.warmup,
+        iterations: args.iterations,
+        deadline: Duration::try_from_secs_f64(args.timeout).ok().filter(|timeout| !timeout.is_zero()),
+    };
+    let inputs = args.inputs.provider();
+    let puzzles = match args.day {
+        Some(day) => vec![registry::find(args.year, day).ok_or(RunError::UnknownDay { year: args.year, day })?],
+        None => registry::year(args.year),
+    };
+    if puzzles.is_empty() {
+        return Err(RunError::UnknownYear(args.year));
+    }
+    let path = args
+        .history
+        .unwrap_or_else(|| aoc_common::repository_root().join(args.year.to_string()).join("bench.json"));
+    let mut history = bench::History::load(&path)?;
+
+    let mut all_timed = true;
+    let mut regressions = 0;
+    let mut timings = Vec::new();
+    for puzzle in puzzles.iter() {
+        let measured = InputSource::from_arg(args.input.as_deref())
+            .read(puzzle, &inputs)
+            .and_then(|input| bench::measure(puzzle, &input, &params, &parts, settings));
+        match measured {
+            Ok(measured) => {
+                for timing in measured.iter() {
+                    let comparison = bench::Comparison {
+                        timing,
+                        previous: history.previous(timing),
+                        threshold: args.threshold,
+                    };
+                    regressions += comparison.is_regression() as usize;
+                    all_timed &= !timing.timeout;
+                    println!("{}", comparison);
+                }
+                timings.extend(measured);
+            }
+            Err(e) => {
+                error!("{} day {}: {}", puzzle.year, puzzle.day, e);
+                all_timed = false;
+            }
+        }
+    }
+
+    if regressions > 0 {
+        warn!("{} phase(s) got more than {}% slower", regressions, args.threshold);
+    }
+    if !args.no_save && !timings.is_empty() {
+        history.push(bench::Run::now(timings));
+        history.save(&path)?;
+    }
+    Ok(all_timed && regressions == 0)
+}
+
+fn new_day(args: NewArgs) -> Result<bool, RunError> {
+    let (year, day) = match (args.numbers.as_slice(), args.year) {
+        (&[year, _], Some(flag)) if flag != year => {
+            return Err(RunError::Usage(format!("year {} and --year {} disagree", year, flag)))
+        }
+        (&[year, day], _) => (year, day),
+        (&[day], Some(year)) => (year, day),
+        (&[day], None) => {
+            let latest = registry::puzzles().iter().map(|puzzle| puzzle.year).max();
+            (latest.ok_or_else(|| RunError::Usage("give the year of the new day".to_string()))?, day)
+        }
+        _ => unreachable!("clap takes one or two numbers"),
+    };
+    if !(1..=25).contains(&day) {
+        return Err(RunError::Usage(format!("there is no day {} in Advent of Code", day)));
+    }
+
+    // only what is cached; a new day's page is often not downloaded yet
+    let example = match args.inputs.provider().offline(true).fetch_page(year, day) {
+        Ok(page) => aoc_common::examples::extract(&page.text).into_iter().next(),
+        Err(e) => {
+            info!("{}, leaving the example empty", e);
+            None
+        }
+    };
+    for path in scaffold::scaffold(&aoc_common::repository_root(), year, day, example)? {
+        println!("{}", path.display());
+    }
+    println!("run it with `cargo run -p aoc -- run {} {}`", year, day);
+    Ok(true)
+}
+
+fn check(args: CheckArgs) -> Result<bool, RunError> {
+    let puzzle = registry::find(args.year, args.day).ok_or(RunError::UnknownDay {
+        year: args.year,
+        day: args.day,
+    })?;
+    let deadline = Duration::try_from_secs_f64(args.settings.timeout)
+        .ok()
+        .filter(|timeout| !timeout.is_zero());
+    let real = if args.settings.input {
+        Some((
+            InputSource::Default.read(&puzzle, &args.inputs.provider())?,
+            KnownAnswers::load(&aoc_common::answers_path(args.year))?,
+        ))
+    } else {
+        None
+    };
+
+    let directory = aoc_common::fixtures_dir(args.year, args.day);
+    let results = watch::check(&puzzle, &directory, real.as_ref().map(|(input, known)| (input, known)), deadline);
+    if args.json {
+        for result in results.iter() {
+            println!("{}", serde_json::to_string(result).expect("results serialize"));
+        }
+    } else {
+        let title = format!("{} day {}", args.year, args.day);
+        println!("{}", watch::summarize(&title, &results, None));
+    }
+    Ok(watch::all_passed(&results))
+}
+
+fn watch(args: WatchArgs) -> Result<bool, RunError> {
+    let puzzle = registry::find(args.year, args.day).ok_or(RunError::UnknownDay {
+        year: args.year,
+        day: args.day,
+    })?;
+    let input = args
+        .settings
+        .input
+        .then(|| args.inputs.provider().cache_path(args.year, args.day));
+    let options = watch::CheckOptions {
+        real_input: args.settings.input,
+        timeout: Some(args.settings.timeout),
+        offline: args.inputs.offline,
+        cache_dir: args.inputs.cache_dir,
+    };
+    watch::watch(&aoc_common::repository_root(), &puzzle, &options, input.as_deref())?;
+    Ok(true)
+}
+
+fn examples(args: ExamplesArgs) -> Result<bool, RunError> {
+    let page = args.inputs.provider().refresh(args.refresh).fetch_page(args.year, args.day)?;
+    let examples = aoc_common::examples::extract(&page.text);
+    if examples.is_empty() {
+        warn!("no examples found in {}", page.path.display());
+        return Ok(false);
+    }
+
+    let directory = aoc_common::fixtures_dir(args.year, args.day);
+    let output_error = |path: &std::path::Path| {
+        let path = path.display().to_string();
+        move |source| RunError::Output { path, source }
+    };
+    fs::create_dir_all(&directory).map_err(output_error(&directory))?;
+    let mut names = Vec::new();
+    for (index, example) in examples.iter().enumerate() {
+        let name = format!("example{}", index + 1);
+        let path = directory.join(format!("{}.toml", name));
+        if path.exists() && !args.force {
+            warn!("{} already exists, use --force to replace it", path.display());
+        } else {
+            fs::write(&path, example.to_toml()).map_err(output_error(&path))?;
+            println!("{}", path.display());
+        }
+        names.push(name);
+    }
+    println!("test them with `aoc_common::example_tests!(Day{}: {});` in src/lib.rs", args.day, names.join(", "));
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let _trace = aoc_common::init_tracing(cli.trace.as_deref());
+
+    let result = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Submit(args) => submit(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+        Command::New(args) => new_day(args),
+        Command::Check(args) => check(args),
+        Command::Watch(args) => watch(args),
+        Command::Examples(args) => examples(args),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use aoc_common::Puzzle;
+
+/// Every solved day, in the order a whole-year run goes through them.
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<day1::Day1>(2024, 1),
+        Puzzle::new::<day2::Day2>(2024, 2),
+        Puzzle::new::<day3::Day3>(2024, 3),
+        Puzzle::new::<day4::Day4>(2024, 4),
+        Puzzle::new::<day5::Day5>(2024, 5),
+        Puzzle::new::<day7::Day7>(2024, 7),
+        Puzzle::new::<day12::Day12>(2024, 12),
+        Puzzle::new::<day13::Day13>(2024, 13),
+        Puzzle::new::<day14::Day14>(2024, 14),
+        Puzzle::new::<day18::Day18>(2024, 18),
+        Puzzle::new::<day19::Day19>(2024, 19),
+    ]
+}
+
+pub fn find(year: u32, day: u32) -> Option<Puzzle> {
+    puzzles()
+        .into_iter()
+        .find(|puzzle| puzzle.year == year && puzzle.day == day)
+}
+
+pub fn year(year: u32) -> Vec<Puzzle> {
+    puzzles()
+        .into_iter()
+        .filter(|puzzle| puzzle.year == year)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_day_is_registered_once() {
+        let puzzles = puzzles();
+        let days: HashSet<_> = puzzles.iter().map(|p| (p.year, p.day)).collect();
+        assert_eq!(days.len(), puzzles.len());
+    }
+
+    #[test]
+    fn year_runs_in_day_order() {
+        let days: Vec<u32> = year(2024).iter().map(|p| p.day).collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn find_knows_parametrised_days() {
+        assert_eq!(find(2024, 14).unwrap().params, ["wide", "tall"]);
+        assert!(find(2024, 6).is_none());
+    }
+}
@@ -0,0 +1,204 @@
+//! Run results as JSON lines or CSV, for scripts and dashboards.
+//!
+//! Every part becomes one record with the same columns in both formats; a
+//! day or input that could not be run becomes a single `error` record
+//! without a part.
+
+use std::io::{self, Write};
+
+use aoc_common::Answer;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::concurrent::DayRun;
+use crate::run::{DayResults, InputRun, RunError, Status};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Answers as sentences, and a table for a whole year
+    Text,
+    /// One JSON object per part
+    Json,
+    /// One CSV row per part, after a header
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordStatus {
+    Solved,
+    /// The day has no solution for the part yet.
+    Unsolved,
+    Timeout,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: Option<usize>,
+    /// Always a string, as answers can be text or outgrow JSON's numbers.
+    pub answer: Option<String>,
+    pub duration_ns: Option<u64>,
+    pub status: RecordStatus,
+    /// Name of the input, unknown when a day's input could not be read.
+    pub input: Option<String>,
+}
+
+impl Record {
+    fn error(year: u32, day: u32, input: Option<String>) -> Record {
+        Record {
+            year,
+            day,
+            part: None,
+            answer: None,
+            duration_ns: None,
+            status: RecordStatus::Error,
+            input,
+        }
+    }
+}
+
+/// One record per part of a day.
+pub fn records(results: &DayResults) -> Vec<Record> {
+    results
+        .parts
+        .iter()
+        .map(|result| {
+            let (answer, status) = match &result.status {
+                Status::Solved(Answer::Unsolved) => (None, RecordStatus::Unsolved),
+                Status::Solved(answer) => (Some(answer.to_string()), RecordStatus::Solved),
+                Status::Timeout => (None, RecordStatus::Timeout),
+            };
+            Record {
+                year: result.year,
+                day: result.day,
+                part: Some(result.part.number()),
+                answer,
+                duration_ns: Some(result.elapsed.as_nanos() as u64),
+                status,
+                input: Some(results.input.clone()),
+            }
+        })
+        .collect()
+}
+
+/// The records of a whole-year run, in day order.
+pub fn year_records(runs: &[DayRun]) -> Vec<Record> {
+    runs.iter()
+        .flat_map(|run| match &run.result {
+            Ok(results) => records(results),
+            Err(_) => vec![Record::error(run.year, run.day, None)],
+        })
+        .collect()
+}
+
+/// The records of one day run on several inputs, in input order.
+pub fn input_records(year: u32, day: u32, runs: &[InputRun]) -> Vec<Record> {
+    runs.iter()
+        .flat_map(|run| match &run.result {
+            Ok(results) => records(results),
+            Err(_) => vec![Record::error(year, day, Some(run.name.clone()))],
+        })
+        .collect()
+}
+
+/// Writes the records in a machine-readable format.
+pub fn write(format: Format, records: &[Record], out: impl Write) -> io::Result<()> {
+    match format {
+        Format::Text => unreachable!("text results are printed by the runner"),
+        Format::Json => {
+            let mut out = out;
+            for record in records {
+                serde_json::to_writer(&mut out, record)?;
+                writeln!(out)?;
+            }
+            out.flush()
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()
+        }
+    }
+}
+
+/// Writes the records to standard output.
+pub fn print(format: Format, records: &[Record]) -> Result<(), RunError> {
+    write(format, records, io::stdout().lock()).map_err(|source| RunError::Output {
+        path: "<stdout>".to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::PartResult;
+    use aoc_common::Part;
+    use std::time::Duration;
+
+    fn sample() -> Vec<Record> {
+        let part = |part, status| PartResult {
+            year: 2024,
+            day: 14,
+            part,
+            status,
+            elapsed: Duration::from_micros(1500),
+            alloc: None,
+        };
+        let results = DayResults {
+            year: 2024,
+            day: 14,
+            input: "d14.txt".to_string(),
+            parse_alloc: None,
+            parts: vec![
+                part(Part::One, Status::Solved(Answer::Number(12))),
+                part(Part::Two, Status::Solved(Answer::Unsolved)),
+            ],
+        };
+        let runs = vec![
+            DayRun {
+                year: 2024,
+                day: 14,
+                result: Ok(results),
+            },
+            DayRun {
+                year: 2024,
+                day: 18,
+                result: Err(RunError::UnknownDay { year: 2024, day: 18 }),
+            },
+        ];
+        year_records(&runs)
+    }
+
+    fn written(format: Format) -> String {
+        let mut out = Vec::new();
+        write(format, &sample(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json_has_one_object_per_line() {
+        assert_eq!(
+            written(Format::Json),
+            "{\"year\":2024,\"day\":14,\"part\":1,\"answer\":\"12\",\"duration_ns\":1500000,\"status\":\"solved\",\"input\":\"d14.txt\"}\n\
+             {\"year\":2024,\"day\":14,\"part\":2,\"answer\":null,\"duration_ns\":1500000,\"status\":\"unsolved\",\"input\":\"d14.txt\"}\n\
+             {\"year\":2024,\"day\":18,\"part\":null,\"answer\":null,\"duration_ns\":null,\"status\":\"error\",\"input\":null}\n"
+        );
+    }
+
+    #[test]
+    fn csv_has_a_header_and_empty_missing_fields() {
+        assert_eq!(
+            written(Format::Csv),
+            "year,day,part,answer,duration_ns,status,input\n\
+             2024,14,1,12,1500000,solved,d14.txt\n\
+             2024,14,2,,1500000,unsolved,d14.txt\n\
+             2024,18,,,,error,\n"
+        );
+    }
+}
@@ -0,0 +1,629 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::cancel::{self, CancelToken};
+use aoc_common::{
+    alloc, AllocStats, Answer, AnswersError, FetchError, InputProvider, Params, ParseError, Parsed, Part, Puzzle, SubmitError,
+};
+use thiserror::Error;
+use tracing::{info_span, Span};
+
+use crate::bench::Phase;
+
+#[derive(Debug, Error)]
+pub enum RunError {
+    #[error("no solution registered for {year} day {day}")]
+    UnknownDay { year: u32, day: u32 },
+    #[error("no solutions registered for {0}")]
+    UnknownYear(u32),
+    #[error("can't read input {path}: {source}")]
+    Input {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("can't write {path}: {source}")]
+    Output {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Fetch(#[from] FetchError),
+    #[error(transparent)]
+    Submit(#[from] SubmitError),
+    #[error(transparent)]
+    Answers(#[from] AnswersError),
+    #[error("{year} day {day}: {source}")]
+    Parse {
+        year: u32,
+        day: u32,
+        #[source]
+        source: ParseError,
+    },
+    #[error("{0}")]
+    Usage(String),
+}
+
+/// A day's input together with the name parse errors should point at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleInput {
+    pub name: String,
+    pub text: String,
+}
+
+/// Where a day's input comes from: the input provider's cache (downloading
+/// it when missing), standard input (`-`), or an explicit path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Default,
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            None => InputSource::Default,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// The sources of `--input` arguments, where a directory stands for
+    /// every file in it, in name order, and must have some. No arguments
+    /// means the default.
+    pub fn expand(args: &[String]) -> Result<Vec<InputSource>, RunError> {
+        if args.is_empty() {
+            return Ok(vec![InputSource::Default]);
+        }
+        if args.iter().filter(|&arg| arg == "-").count() > 1 {
+            return Err(RunError::Usage("standard input can only be read once".to_string()));
+        }
+        let mut sources = Vec::new();
+        for arg in args {
+            let path = Path::new(arg);
+            if arg != "-" && path.is_dir() {
+                let files = files_in(path)?;
+                if files.is_empty() {
+                    return Err(RunError::Usage(format!("no input files in {}", arg)));
+                }
+                sources.extend(files.into_iter().map(InputSource::File));
+            } else {
+                sources.push(InputSource::from_arg(Some(arg)));
+            }
+        }
+        Ok(sources)
+    }
+
+    /// What the input is called before it is read.
+    pub fn name(&self) -> String {
+        match self {
+            InputSource::Default => "cached input".to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::File(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn read(&self, puzzle: &Puzzle, inputs: &InputProvider) -> Result<PuzzleInput, RunError> {
+        let _span = info_span!("input", year = puzzle.year, day = puzzle.day).entered();
+        match self {
+            InputSource::Default => {
+                let input = inputs.fetch(puzzle.year, puzzle.day)?;
+                Ok(PuzzleInput {
+                    name: input.path.display().to_string(),
+                    text: input.text,
+                })
+            }
+            InputSource::File(path) => read_file(path.clone()),
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|source| RunError::Input {
+                        path: "-".to_string(),
+                        source,
+                    })?;
+                Ok(PuzzleInput {
+                    name: "<stdin>".to_string(),
+                    text,
+                })
+            }
+        }
+    }
+}
+
+/// The files directly in `directory`, skipping hidden ones, sorted.
+fn files_in(directory: &Path) -> Result<Vec<PathBuf>, RunError> {
+    let input_error = |source| RunError::Input {
+        path: directory.display().to_string(),
+        source,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory).map_err(input_error)? {
+        let path = entry.map_err(input_error)?.path();
+        let hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_file() && !hidden {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn read_file(path: PathBuf) -> Result<PuzzleInput, RunError> {
+    let name = path.display().to_string();
+    match aoc_common::read_to_string(&path) {
+        Ok(text) => Ok(PuzzleInput { name, text }),
+        Err(source) => Err(RunError::Input { path: name, source }),
+    }
+}
+
+/// Parses a day's input, pointing parse errors at the input's name.
+pub fn parse(puzzle: &Puzzle, input: &PuzzleInput, params: &Params) -> Result<Box<dyn Parsed>, RunError> {
+    let _span = info_span!("parse", year = puzzle.year, day = puzzle.day).entered();
+    puzzle
+        .parse(&input.text, params)
+        .map_err(|source| RunError::Parse {
+            year: puzzle.year,
+            day: puzzle.day,
+            source: source.with_file(&input.name),
+        })
+}
+
+/// Parses the input once and answers the requested parts from it.
+pub fn solve(
+    puzzle: &Puzzle,
+    input: &PuzzleInput,
+    params: &Params,
+    parts: &[Part],
+) -> Result<Vec<(Part, Answer)>, RunError> {
+    let parsed = parse(puzzle, input, params)?;
+    Ok(parts
+        .iter()
+        .map(|&part| (part, part_span(puzzle, part).in_scope(|| parsed.solve(part))))
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Solved(Answer),
+    /// The part was still running when its deadline passed.
+    Timeout,
+}
+
+/// How one part of a day went, and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub status: Status,
+    pub elapsed: Duration,
+    /// What the part allocated, when allocations are counted.
+    pub alloc: Option<AllocStats>,
+}
+
+impl fmt::Display for PartResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {} part {}: ", self.year, self.day, self.part)?;
+        match &self.status {
+            Status::Solved(answer) => write!(f, "{}", answer),
+            Status::Timeout => write!(f, "TIMEOUT after {:.1?}", self.elapsed),
+        }
+    }
+}
+
+/// The parts of one day, and what parsing its input allocated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayResults {
+    pub year: u32,
+    pub day: u32,
+    /// Name of the input the parts were solved for.
+    pub input: String,
+    pub parse_alloc: Option<AllocStats>,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResults {
+    /// Whether every part finished before its deadline.
+    pub fn finished(&self) -> bool {
+        self.parts.iter().all(|result| result.status != Status::Timeout)
+    }
+}
+
+fn part_span(puzzle: &Puzzle, part: Part) -> Span {
+    info_span!("part", year = puzzle.year, day = puzzle.day, part = part.number())
+}
+
+/// Something every thread solving a part holds on to until it exits, such
+/// as the day's slot in a concurrent run.
+pub type Slot = Arc<dyn Send + Sync>;
+
+/// Like `solve`, but gives every part at most `deadline` (if any) and
+/// reports a part that overruns it as timed out instead of waiting for it.
+pub fn solve_within(
+    puzzle: &Puzzle,
+    input: &PuzzleInput,
+    params: &Params,
+    parts: &[Part],
+    deadline: Option<Duration>,
+) -> Result<DayResults, RunError> {
+    solve_in_slot(puzzle, input, params, parts, deadline, Arc::new(()))
+}
+
+/// Like `solve_within`, keeping `slot` until the last part is done, which
+/// for a part that timed out is when its thread winds down.
+pub fn solve_in_slot(
+    puzzle: &Puzzle,
+    input: &PuzzleInput,
+    params: &Params,
+    parts: &[Part],
+    deadline: Option<Duration>,
+    slot: Slot,
+) -> Result<DayResults, RunError> {
+    let (parsed, parse_alloc) = alloc::measure(|| parse(puzzle, input, params));
+    let parsed: Arc<dyn Parsed> = Arc::from(parsed?);
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let started = Instant::now();
+            let (status, alloc) = solve_parsed(puzzle, &parsed, part, deadline, &slot);
+            PartResult {
+                year: puzzle.year,
+                day: puzzle.day,
+                part,
+                status,
+                elapsed: started.elapsed(),
+                alloc,
+            }
+        })
+        .collect();
+    Ok(DayResults {
+        year: puzzle.year,
+        day: puzzle.day,
+        input: input.name.clone(),
+        parse_alloc,
+        parts,
+    })
+}
+
+/// Solves one part of a parsed input: in place without a deadline, else on
+/// its own thread, holding `slot`, until the deadline.
+pub fn solve_parsed(
+    puzzle: &Puzzle,
+    parsed: &Arc<dyn Parsed>,
+    part: Part,
+    deadline: Option<Duration>,
+    slot: &Slot,
+) -> (Status, Option<AllocStats>) {
+    let span = part_span(puzzle, part);
+    match deadline {
+        Some(deadline) => solve_part(puzzle, Arc::clone(parsed), part, span, deadline, Arc::clone(slot)),
+        None => {
+            let (answer, alloc) = span.in_scope(|| alloc::measure(|| parsed.solve(part)));
+            (Status::Solved(answer), alloc)
+        }
+    }
+}
+
+/// Solves a part on its own thread. When the deadline passes the part's
+/// token is cancelled and the thread is left behind with `slot`; parts that
+/// poll the token wind down, the others are stopped by the process exiting.
+fn solve_part(
+    puzzle: &Puzzle,
+    parsed: Arc<dyn Parsed>,
+    part: Part,
+    span: Span,
+    deadline: Duration,
+    slot: Slot,
+) -> (Status, Option<AllocStats>) {
+    let token = CancelToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let worker = {
+        let token = token.clone();
+        thread::Builder::new()
+            .name(format!("{} day {} part {}", puzzle.year, puzzle.day, part))
+            .spawn(move || {
+                let _slot = slot;
+                let solved = span.in_scope(|| cancel::with_token(&token, || alloc::measure(|| parsed.solve(part))));
+                // the runner is gone when the part timed out
+                let _ = sender.send(solved);
+            })
+            .expect("can't start a thread for the part")
+    };
+
+    match receiver.recv_timeout(deadline) {
+        Ok((answer, alloc)) => (Status::Solved(answer), alloc),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            token.cancel();
+            (Status::Timeout, None)
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(panic) => panic::resume_unwind(panic),
+            Ok(()) => unreachable!("the part finished without an answer"),
+        },
+    }
+}
+
+/// How one of several inputs of a day went.
+#[derive(Debug)]
+pub struct InputRun {
+    pub name: String,
+    pub result: Result<DayResults, RunError>,
+}
+
+/// Reads every source and solves the parts for each.
+pub fn solve_inputs(
+    puzzle: &Puzzle,
+    sources: &[InputSource],
+    inputs: &InputProvider,
+    params: &Params,
+    parts: &[Part],
+    deadline: Option<Duration>,
+) -> Vec<InputRun> {
+    sources
+        .iter()
+        .map(|source| InputRun {
+            name: source.name(),
+            result: source
+                .read(puzzle, inputs)
+                .and_then(|input| solve_within(puzzle, &input, params, parts, deadline)),
+        })
+        .collect()
+}
+
+/// A table of the answers, one row per input and one column per part.
+/// Inputs that failed get `ERROR`; the error itself is logged.
+pub fn inputs_table(runs: &[InputRun], parts: &[Part]) -> String {
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .map(|run| {
+            let mut row = vec![run.name.clone()];
+            match &run.result {
+                Ok(results) => row.extend(results.parts.iter().map(|result| match &result.status {
+                    Status::Solved(answer) => answer.to_string(),
+                    Status::Timeout => "TIMEOUT".to_string(),
+                })),
+                Err(_) => row.push("ERROR".to_string()),
+            }
+            row
+        })
+        .collect();
+    let header: Vec<String> = std::iter::once("input".to_string())
+        .chain(parts.iter().map(|part| format!("part {}", part)))
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(String::len).collect();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    std::iter::once(&header)
+        .chain(rows.iter())
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, &width)| format!("{:<width$}", cell))
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prints each part's answer or timeout, followed by what each phase
+/// allocated when that is counted; false when any part timed out.
+pub fn print_results(results: &DayResults) -> bool {
+    for result in results.parts.iter() {
+        println!("{}", result);
+    }
+    print_allocations(results);
+    results.finished()
+}
+
+/// Prints what parsing and each part allocated, when that is counted.
+pub fn print_allocations(results: &DayResults) {
+    let phases = results
+        .parts
+        .iter()
+        .map(|result| (Phase::of(result.part), result.alloc));
+    for (phase, stats) in std::iter::once((Phase::Parse, results.parse_alloc)).chain(phases) {
+        if let Some(stats) = stats {
+            println!("{} day {:>2} {:<6}  {}", results.year, results.day, phase, stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn default_input_comes_from_the_provider_cache() {
+        let cache = std::env::temp_dir().join(format!("aoc-run-{}", std::process::id()));
+        let inputs = InputProvider::default().cache_dir(&cache).offline(true);
+        let puzzle = registry::find(2024, 1).unwrap();
+
+        let error = InputSource::Default.read(&puzzle, &inputs).unwrap_err();
+        assert!(matches!(error, RunError::Fetch(FetchError::NotCached { day: 1, .. })));
+
+        std::fs::create_dir_all(cache.join("2024/d1")).unwrap();
+        std::fs::write(cache.join("2024/d1/input.txt"), "1   2\n").unwrap();
+        let input = InputSource::Default.read(&puzzle, &inputs).unwrap();
+        assert_eq!(input.text, "1   2\n");
+        assert!(input.name.ends_with("input.txt"));
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn input_source_from_arg() {
+        assert_eq!(InputSource::from_arg(None), InputSource::Default);
+        assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("d1.txt")),
+            InputSource::File(PathBuf::from("d1.txt"))
+        );
+    }
+
+    #[test]
+    fn expand_lists_directories_in_name_order() {
+        let directory = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("nested")).unwrap();
+        for name in ["bob.txt", "alice.txt", ".hidden"] {
+            std::fs::write(directory.join(name), "1   2\n").unwrap();
+        }
+        let args = vec!["-".to_string(), directory.display().to_string(), "extra.txt".to_string()];
+
+        let sources = InputSource::expand(&args).unwrap();
+        assert_eq!(
+            sources,
+            vec![
+                InputSource::Stdin,
+                InputSource::File(directory.join("alice.txt")),
+                InputSource::File(directory.join("bob.txt")),
+                InputSource::File(PathBuf::from("extra.txt")),
+            ]
+        );
+        assert_eq!(InputSource::expand(&[]).unwrap(), vec![InputSource::Default]);
+        let twice = InputSource::expand(&["-".to_string(), "-".to_string()]).unwrap_err();
+        assert!(matches!(twice, RunError::Usage(_)));
+
+        let nested = directory.join("nested").display().to_string();
+        let empty = InputSource::expand(std::slice::from_ref(&nested)).unwrap_err();
+        assert_eq!(empty.to_string(), format!("no input files in {}", nested));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn several_inputs_get_a_row_each() {
+        let directory = std::env::temp_dir().join(format!("aoc-team-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("alice.txt"), "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        std::fs::write(directory.join("bob.txt"), "1   1\n").unwrap();
+        let puzzle = registry::find(2024, 1).unwrap();
+        let mut sources = InputSource::expand(&[directory.display().to_string()]).unwrap();
+        sources.push(InputSource::File(directory.join("carol.txt")));
+        let inputs = InputProvider::default().offline(true);
+
+        let runs = solve_inputs(&puzzle, &sources, &inputs, &Params::new(), &Part::ALL, None);
+        assert!(matches!(runs[2].result, Err(RunError::Input { .. })));
+        let table = inputs_table(&runs, &Part::ALL);
+        let rows: Vec<Vec<&str>> = table.lines().map(|line| line.split_whitespace().collect()).collect();
+        assert_eq!(rows[0], vec!["input", "part", "1", "part", "2"]);
+        assert_eq!(rows[1][1..], ["11", "31"]);
+        assert_eq!(rows[2][1..], ["0", "1"]);
+        assert_eq!(rows[3][1..], ["ERROR"]);
+        assert!(rows[3][0].ends_with("carol.txt"));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn sample(text: &str) -> PuzzleInput {
+        PuzzleInput {
+            name: "sample.txt".to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn solve_answers_only_requested_parts() {
+        let puzzle = registry::find(2024, 1).unwrap();
+        let input = sample("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+
+        let answers = solve(&puzzle, &input, &Params::new(), &[Part::Two]).unwrap();
+        assert_eq!(answers, vec![(Part::Two, Answer::Number(31))]);
+    }
+
+    #[test]
+    fn solve_passes_params_to_the_day() {
+        let puzzle = registry::find(2024, 14).unwrap();
+        let input = sample(
+            "p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\n\
+             p=0,0 v=1,3\np=3,0 v=-2,-2\np=7,6 v=-1,-3\np=3,0 v=-1,-2\n\
+             p=9,3 v=2,3\np=7,3 v=-1,2\np=2,4 v=2,-3\np=9,5 v=-3,-3\n",
+        );
+        let params = Params::new().with("wide", 11).with("tall", 7);
+
+        let answers = solve(&puzzle, &input, &params, &[Part::One]).unwrap();
+        assert_eq!(answers, vec![(Part::One, Answer::Number(12))]);
+    }
+
+    #[test]
+    fn solve_reports_unknown_params() {
+        let puzzle = registry::find(2024, 1).unwrap();
+        let params = Params::new().with("wide", 11);
+
+        let error = solve(&puzzle, &sample("1   2\n"), &params, &Part::ALL).unwrap_err();
+        assert!(matches!(error, RunError::Parse { day: 1, .. }));
+    }
+
+    #[test]
+    fn solve_points_strict_parse_errors_at_the_input_file() {
+        let puzzle = registry::find(2024, 2).unwrap();
+        let params = Params::new().strict(true);
+
+        let error = solve(&puzzle, &sample("7 6 4\n1 x 7\n"), &params, &Part::ALL).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "2024 day 2: sample.txt:2:3: expected level, found `x`"
+        );
+    }
+
+    /// Spins until cancelled when asked for part 2.
+    struct Stubborn;
+
+    impl aoc_common::Solution for Stubborn {
+        type Input = i64;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.trim().parse().map_err(|_| ParseError::new("not a number"))
+        }
+
+        fn part1(number: &Self::Input) -> Answer {
+            (*number).into()
+        }
+
+        fn part2(_: &Self::Input) -> Answer {
+            while !cancel::cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn solve_within_reports_parts_past_the_deadline() {
+        let puzzle = Puzzle::new::<Stubborn>(2024, 99);
+        let deadline = Duration::from_millis(50);
+
+        let results = solve_within(&puzzle, &sample("7"), &Params::new(), &Part::ALL, Some(deadline)).unwrap();
+        assert!(!print_results(&results));
+        let results = results.parts;
+        assert_eq!(results[0].status, Status::Solved(Answer::Number(7)));
+        assert_eq!(results[1].status, Status::Timeout);
+        assert!(results[1].elapsed >= deadline);
+        assert!(results[1].to_string().starts_with("2024 day 99 part 2: TIMEOUT after "), "{}", results[1]);
+        assert_eq!(results[1].alloc, None);
+    }
+
+    #[test]
+    fn solve_within_without_deadline_answers_in_place() {
+        let puzzle = registry::find(2024, 1).unwrap();
+        let input = sample("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+
+        let results = solve_within(&puzzle, &input, &Params::new(), &[Part::One], None).unwrap();
+        assert_eq!(results.parse_alloc.is_some(), alloc::is_counting());
+        let results = results.parts;
+        assert_eq!(results[0].status, Status::Solved(Answer::Number(11)));
+        assert_eq!(results[0].to_string(), "2024 day 1 part 1: 11");
+    }
+}
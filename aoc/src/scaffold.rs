@@ -0,0 +1,329 @@
+//! `aoc new`: lays out a new day's crate and wires it into the workspace.
+//!
+//! The generated crate has the same shape as the existing days: a `lib.rs`
+//! with the `Solution`, a small `main.rs` reading the cached input, and an
+//! example fixture. The root `Cargo.toml`, the runner's `Cargo.toml` and
+//! `registry.rs` are edited as text so their layout and comments survive.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Example;
+
+use crate::run::RunError;
+
+/// The year the crates were first written for keeps the plain `dayN` names.
+const FIRST_YEAR: u32 = 2024;
+
+const CARGO_TOML: &str = r#"[package]
+name = "{crate}"
+version.workspace = true
+edition.workspace = true
+
+[dependencies]
+aoc-common.workspace = true
+tracing.workspace = true
+"#;
+
+const LIB_RS: &str = r#"use aoc_common::parse::numbered_lines;
+use aoc_common::{Answer, ParseError, Solution};
+
+pub type Lines = Vec<String>;
+
+fn read_lines(input: &str) -> Result<Lines, ParseError> {
+    let mut lines = Vec::new();
+
+    for (_line_number, line) in numbered_lines(input) {
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    Ok(lines)
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Lines;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_lines(input)
+    }
+
+    fn part1(_lines: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+aoc_common::example_tests!(Day{day}: example1);
+"#;
+
+const MAIN_RS: &str = r#"use aoc_common::{InputProvider, Solution};
+use {lib}::Day{day};
+use tracing::info;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    aoc_common::init_logger();
+
+    let input = InputProvider::from_env().fetch({year}, {day})?;
+    info!("Read source data from {}", input.path.display());
+
+    let lines = Day{day}::parse(&input.text).map_err(|e| e.with_file(&input.path))?;
+
+    let first_result = Day{day}::part1(&lines);
+    aoc_common::print_result(1, "", first_result);
+
+    let second_result = Day{day}::part2(&lines);
+    aoc_common::print_result(2, "", second_result);
+
+    Ok(())
+}
+"#;
+
+const ANSWERS_TOML: &str = r#"# Confirmed answers for the real inputs, checked by `aoc verify {year}` and by
+# `cargo test` for every input cached on the machine. One table per day, e.g.
+#
+# [day1]
+# part1 = 123
+# part2 = 456
+#
+# Use `[[dayN]]` entries with `input = "<sha256sum of input.txt>"` to keep the
+# answers of several inputs apart.
+"#;
+
+/// Package name of a day's crate.
+pub fn crate_name(year: u32, day: u32) -> String {
+    if year == FIRST_YEAR {
+        format!("day{}", day)
+    } else {
+        format!("day{}-{}", day, year)
+    }
+}
+
+fn fill(template: &str, year: u32, day: u32) -> String {
+    let name = crate_name(year, day);
+    template
+        .replace("{crate}", &name)
+        .replace("{lib}", &name.replace('-', "_"))
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+}
+
+fn read(path: &Path) -> Result<String, RunError> {
+    fs::read_to_string(path).map_err(|source| RunError::Input {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+fn write(path: &Path, text: &str) -> Result<(), RunError> {
+    let output_error = |source| RunError::Output {
+        path: path.display().to_string(),
+        source,
+    };
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(output_error)?;
+    }
+    fs::write(path, text).map_err(output_error)
+}
+
+/// Creates `YEAR/dN/rust` under `root` and registers it, returning every
+/// file written or changed. `example` pre-fills the first fixture.
+pub fn scaffold(root: &Path, year: u32, day: u32, example: Option<Example>) -> Result<Vec<PathBuf>, RunError> {
+    let name = crate_name(year, day);
+    let directory = root.join(year.to_string()).join(format!("d{}", day)).join("rust");
+    if directory.exists() {
+        return Err(RunError::Usage(format!("{} already exists", directory.display())));
+    }
+
+    let workspace = root.join("Cargo.toml");
+    let manifest = register_in_workspace(&read(&workspace)?, year, day)?;
+    let runner = root.join("aoc").join("Cargo.toml");
+    let runner_manifest = register_in_runner(&read(&runner)?, &name)?;
+    let registry = root.join("aoc").join("src").join("registry.rs");
+    let registry_source = register_puzzle(&read(&registry)?, year, day)?;
+
+    let mut written = Vec::new();
+    let mut create = |path: PathBuf, text: &str| -> Result<(), RunError> {
+        write(&path, text)?;
+        written.push(path);
+        Ok(())
+    };
+    create(directory.join("Cargo.toml"), &fill(CARGO_TOML, year, day))?;
+    create(directory.join("src").join("lib.rs"), &fill(LIB_RS, year, day))?;
+    create(directory.join("src").join("main.rs"), &fill(MAIN_RS, year, day))?;
+    let example = example.unwrap_or_default();
+    create(directory.join("fixtures").join("example1.toml"), &example.to_toml())?;
+
+    let answers = root.join(year.to_string()).join("answers.toml");
+    let mut answers_text = if answers.is_file() {
+        read(&answers)?
+    } else {
+        fill(ANSWERS_TOML, year, day)
+    };
+    if !answers_text.lines().any(|line| line.trim() == format!("[day{}]", day)) {
+        answers_text.push_str(&format!("\n[day{}]\n", day));
+        create(answers, &answers_text)?;
+    }
+
+    create(workspace, &manifest)?;
+    create(runner, &runner_manifest)?;
+    create(registry, &registry_source)?;
+    Ok(written)
+}
+
+/// Adds the year to the workspace members and the day to its dependencies.
+fn register_in_workspace(manifest: &str, year: u32, day: u32) -> Result<String, RunError> {
+    let name = crate_name(year, day);
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+
+    let members = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("members"))
+        .ok_or_else(|| RunError::Usage("no workspace members in Cargo.toml".to_string()))?;
+    let pattern = format!("\"{}/d*/rust\"", year);
+    if !lines[members].contains(&pattern) {
+        let close = lines[members]
+            .rfind(']')
+            .ok_or_else(|| RunError::Usage("workspace members must fit on one line".to_string()))?;
+        lines[members].insert_str(close, &format!(", {}", pattern));
+    }
+
+    let entry = format!("{} = {{ path = \"{}/d{}/rust\" }}", name, year, day);
+    if lines.iter().any(|line| line.starts_with(&format!("{} =", name))) {
+        return Err(RunError::Usage(format!("{} is already a workspace dependency", name)));
+    }
+    // after the last day, so the days stay together
+    let last_day = lines
+        .iter()
+        .rposition(|line| line.starts_with("day") && line.contains("path ="))
+        .or_else(|| lines.iter().position(|line| line.starts_with("aoc-common")))
+        .ok_or_else(|| RunError::Usage("no aoc-common in the workspace dependencies".to_string()))?;
+    lines.insert(last_day + 1, entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day's crate to the runner's dependencies.
+fn register_in_runner(manifest: &str, name: &str) -> Result<String, RunError> {
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+    let last_day = lines
+        .iter()
+        .rposition(|line| line.starts_with("day") && line.ends_with(".workspace = true"))
+        .or_else(|| lines.iter().rposition(|line| line.ends_with(".workspace = true")))
+        .ok_or_else(|| RunError::Usage("no dependencies in aoc/Cargo.toml".to_string()))?;
+    lines.insert(last_day + 1, format!("{}.workspace = true", name));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day to `puzzles()`, keeping the list ordered by year and day.
+fn register_puzzle(source: &str, year: u32, day: u32) -> Result<String, RunError> {
+    let registered = |line: &str| -> Option<(u32, u32)> {
+        let arguments = line.trim().strip_prefix("Puzzle::new::<")?.split_once(">(")?.1;
+        let (y, d) = arguments.trim_end_matches("),").split_once(',')?;
+        Some((y.trim().parse().ok()?, d.trim().parse().ok()?))
+    };
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+    let entries: Vec<(usize, (u32, u32))> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, registered(line)?)))
+        .collect();
+    if entries.iter().any(|&(_, entry)| entry == (year, day)) {
+        return Err(RunError::Usage(format!("{} day {} is already registered", year, day)));
+    }
+    let &(last, _) = entries
+        .last()
+        .ok_or_else(|| RunError::Usage("no puzzles in registry.rs".to_string()))?;
+    let at = entries
+        .iter()
+        .find(|&&(_, entry)| entry > (year, day))
+        .map_or(last + 1, |&(index, _)| index);
+
+    let indent: String = lines[last].chars().take_while(|c| c.is_whitespace()).collect();
+    let lib = crate_name(year, day).replace('-', "_");
+    lines.insert(
+        at,
+        format!("{}Puzzle::new::<{}::Day{}>({}, {}),", indent, lib, day, year, day),
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str = r#"[workspace]
+resolver = "2"
+members = ["common", "aoc", "2024/d*/rust"]
+
+[workspace.dependencies]
+aoc-common = { path = "common" }
+day1 = { path = "2024/d1/rust" }
+day19 = { path = "2024/d19/rust" }
+clap = { version = "4", features = ["derive"] }
+"#;
+
+    const RUNNER: &str = "[dependencies]\naoc-common.workspace = true\nday1.workspace = true\nday19.workspace = true\n";
+
+    const REGISTRY: &str = r#"pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<day1::Day1>(2024, 1),
+        Puzzle::new::<day19::Day19>(2024, 19),
+    ]
+}
+"#;
+
+    #[test]
+    fn registers_next_day_of_a_year() {
+        let manifest = register_in_workspace(WORKSPACE, 2024, 20).unwrap();
+        assert!(manifest.contains("day19 = { path = \"2024/d19/rust\" }\nday20 = { path = \"2024/d20/rust\" }\nclap"));
+        assert!(manifest.contains("members = [\"common\", \"aoc\", \"2024/d*/rust\"]\n"));
+
+        let runner = register_in_runner(RUNNER, "day20").unwrap();
+        assert!(runner.ends_with("day19.workspace = true\nday20.workspace = true\n"));
+
+        let registry = register_puzzle(REGISTRY, 2024, 12).unwrap();
+        assert!(registry.contains(
+            "(2024, 1),\n        Puzzle::new::<day12::Day12>(2024, 12),\n        Puzzle::new::<day19::Day19>(2024, 19),"
+        ));
+        assert!(register_puzzle(REGISTRY, 2024, 19).is_err());
+    }
+
+    #[test]
+    fn new_year_gets_its_own_members_and_crate_names() {
+        let manifest = register_in_workspace(WORKSPACE, 2025, 1).unwrap();
+        assert!(manifest.contains("members = [\"common\", \"aoc\", \"2024/d*/rust\", \"2025/d*/rust\"]"));
+        assert!(manifest.contains("day1-2025 = { path = \"2025/d1/rust\" }"));
+
+        let registry = register_puzzle(REGISTRY, 2025, 1).unwrap();
+        assert!(registry.contains("(2024, 19),\n        Puzzle::new::<day1_2025::Day1>(2025, 1),\n    ]"));
+        assert!(fill(MAIN_RS, 2025, 1).contains("use day1_2025::Day1;"));
+    }
+
+    #[test]
+    fn scaffold_writes_the_crate_and_registers_it() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root.join("Cargo.toml"), WORKSPACE).unwrap();
+        write(&root.join("aoc/Cargo.toml"), RUNNER).unwrap();
+        write(&root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+        let example = Example {
+            part1: Some("4".to_string()),
+            input: "a\nb\n".to_string(),
+            ..Example::default()
+        };
+
+        let written = scaffold(&root, 2025, 3, Some(example.clone())).unwrap();
+        assert_eq!(written.len(), 8);
+        let day = root.join("2025/d3/rust");
+        assert!(read(&day.join("src/lib.rs")).unwrap().contains("pub struct Day3;"));
+        assert_eq!(Example::parse(&read(&day.join("fixtures/example1.toml")).unwrap()).unwrap(), example);
+        let answers = read(&root.join("2025/answers.toml")).unwrap();
+        assert!(answers.starts_with("# Confirmed answers") && answers.ends_with("\n[day3]\n"));
+        assert!(read(&root.join("aoc/src/registry.rs")).unwrap().contains("day3_2025::Day3>(2025, 3)"));
+
+        let error = scaffold(&root, 2025, 3, None).unwrap_err();
+        assert!(error.to_string().ends_with("already exists"), "{}", error);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+use std::time::Duration;
+
+use aoc_common::{input_hash, Answer, InputProvider, KnownAnswers, Params, Part, Puzzle};
+
+use crate::run::{self, InputSource, RunError, Status};
+
+/// How one part's answer compares with the confirmed one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Match,
+    Mismatch { expected: String },
+    /// Nothing is recorded for this part of this input yet.
+    Unknown,
+    /// The part didn't finish before its deadline.
+    Timeout,
+}
+
+impl Outcome {
+    /// Whether the check doesn't count against the answers.
+    pub fn passed(&self) -> bool {
+        matches!(self, Outcome::Match | Outcome::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub answer: Answer,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.outcome == Outcome::Timeout {
+            return write!(f, "{} day {} part {}: TIMEOUT", self.year, self.day, self.part);
+        }
+        write!(f, "{} day {} part {}: {} ", self.year, self.day, self.part, self.answer)?;
+        match &self.outcome {
+            Outcome::Match => write!(f, "ok"),
+            Outcome::Mismatch { expected } => write!(f, "MISMATCH, expected {}", expected),
+            Outcome::Unknown => write!(f, "(no known answer)"),
+            Outcome::Timeout => Ok(()),
+        }
+    }
+}
+
+/// Solves a day's real input, every part within `deadline`, and compares it
+/// with the known answers. Days without any known answers are not solved
+/// at all.
+pub fn verify(
+    puzzle: &Puzzle,
+    inputs: &InputProvider,
+    known: &KnownAnswers,
+    deadline: Option<Duration>,
+) -> Result<Vec<Check>, RunError> {
+    if !known.days().any(|day| day == puzzle.day) {
+        return Ok(Vec::new());
+    }
+    let input = InputSource::Default.read(puzzle, inputs)?;
+    let entry = known.lookup(puzzle.day, &input_hash(&input.text));
+    let results = run::solve_within(puzzle, &input, &Params::new(), &Part::ALL, deadline)?;
+
+    Ok(results
+        .parts
+        .into_iter()
+        .map(|result| {
+            let part = result.part;
+            let (answer, outcome) = match result.status {
+                Status::Timeout => (Answer::Unsolved, Outcome::Timeout),
+                Status::Solved(answer) => {
+                    let outcome = match entry.and_then(|entry| entry.expected(part)) {
+                        Some(expected) if expected == answer.to_string() => Outcome::Match,
+                        Some(expected) => Outcome::Mismatch {
+                            expected: expected.to_string(),
+                        },
+                        None => Outcome::Unknown,
+                    };
+                    (answer, outcome)
+                }
+            };
+            Check {
+                year: puzzle.year,
+                day: puzzle.day,
+                part,
+                answer,
+                outcome,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use aoc_common::{cancel, ParseError, Solution};
+    use std::{env, fs};
+
+    const SAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    fn cached_sample(name: &str) -> (InputProvider, std::path::PathBuf) {
+        let cache = env::temp_dir().join(format!("aoc-verify-{}-{}", name, std::process::id()));
+        fs::create_dir_all(cache.join("2024/d1")).unwrap();
+        fs::write(cache.join("2024/d1/input.txt"), SAMPLE).unwrap();
+        (InputProvider::default().cache_dir(&cache).offline(true), cache)
+    }
+
+    #[test]
+    fn verify_compares_with_known_answers() {
+        let (inputs, cache) = cached_sample("compare");
+        let puzzle = registry::find(2024, 1).unwrap();
+        let known = KnownAnswers::parse("[day1]\npart1 = 11\npart2 = 30\n").unwrap();
+
+        let checks = verify(&puzzle, &inputs, &known, None).unwrap();
+        assert_eq!(checks[0].outcome, Outcome::Match);
+        assert_eq!(
+            checks[1].outcome,
+            Outcome::Mismatch {
+                expected: "30".to_string()
+            }
+        );
+        assert_eq!(checks[1].to_string(), "2024 day 1 part 2: 31 MISMATCH, expected 30");
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn verify_picks_answers_by_input_hash() {
+        let (inputs, cache) = cached_sample("hash");
+        let puzzle = registry::find(2024, 1).unwrap();
+        let known = KnownAnswers::parse(&format!(
+            "[[day1]]\ninput = \"{}\"\npart1 = 11\n\n[[day1]]\ninput = \"other\"\npart1 = 7\n",
+            input_hash(SAMPLE)
+        ))
+        .unwrap();
+
+        let checks = verify(&puzzle, &inputs, &known, None).unwrap();
+        assert_eq!(checks[0].outcome, Outcome::Match);
+        assert_eq!(checks[1].outcome, Outcome::Unknown);
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn verify_fails_parts_past_the_deadline() {
+        let cache = env::temp_dir().join(format!("aoc-verify-timeout-{}", std::process::id()));
+        fs::create_dir_all(cache.join("2024/d99")).unwrap();
+        fs::write(cache.join("2024/d99/input.txt"), "").unwrap();
+        let inputs = InputProvider::default().cache_dir(&cache).offline(true);
+        let puzzle = Puzzle::new::<Stalls>(2024, 99);
+        let known = KnownAnswers::parse("[day99]\npart1 = 1\npart2 = 2\n").unwrap();
+
+        let checks = verify(&puzzle, &inputs, &known, Some(Duration::from_millis(20))).unwrap();
+        assert_eq!(checks[0].outcome, Outcome::Match);
+        assert_eq!(checks[1].outcome, Outcome::Timeout);
+        assert!(!checks[1].outcome.passed());
+        assert_eq!(checks[1].to_string(), "2024 day 99 part 2: TIMEOUT");
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    /// Part 2 waits until it is cancelled.
+    struct Stalls;
+
+    impl Solution for Stalls {
+        type Input = ();
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Answer {
+            Answer::Number(1)
+        }
+
+        fn part2(_: &Self::Input) -> Answer {
+            while !cancel::cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn verify_skips_days_without_known_answers() {
+        let puzzle = registry::find(2024, 4).unwrap();
+        let known = KnownAnswers::parse("[day1]\npart1 = 11\n").unwrap();
+
+        assert!(verify(&puzzle, &InputProvider::default(), &known, None).unwrap().is_empty());
+    }
+
+    /// Checks the committed answers against every input that is cached on
+    /// this machine, so a refactor that changes an answer fails the tests.
+    #[test]
+    fn committed_answers_still_hold() {
+        let inputs = InputProvider::default().offline(true);
+        let known = KnownAnswers::load(&aoc_common::answers_path(2024)).unwrap();
+
+        for puzzle in registry::year(2024) {
+            if !inputs.cache_path(puzzle.year, puzzle.day).is_file() {
+                continue;
+            }
+            for check in verify(&puzzle, &inputs, &known, Some(Duration::from_secs(60))).unwrap() {
+                assert!(check.outcome.passed(), "{}", check);
+            }
+        }
+    }
+}
@@ -0,0 +1,463 @@
+//! `aoc check` runs a day's example fixtures (and optionally its real input)
+//! and `aoc watch` reruns that check whenever the day's code changes.
+//!
+//! The running binary can't pick up a changed solution, so `watch` starts
+//! `cargo run -p aoc -- check --json` after every change and compares the
+//! results it prints with the ones of the previous run.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use aoc_common::{input_hash, Example, KnownAnswers, Params, Part, Puzzle};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::bench::nanos;
+use crate::run::{self, PuzzleInput, RunError, Status};
+
+/// Changes that arrive this close together trigger a single rerun.
+const SETTLE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Pass,
+    Fail,
+    /// Nothing to compare with, as for a real input without known answers.
+    Answered,
+    Timeout,
+    /// The case couldn't be solved at all, like a fixture that doesn't parse.
+    Error,
+}
+
+/// One part of one example, or of the real input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseResult {
+    /// Fixture name, or `input` for the real input.
+    pub case: String,
+    /// Missing when the whole case failed before any part ran.
+    pub part: Option<usize>,
+    pub outcome: Outcome,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub elapsed_ns: u64,
+}
+
+impl CaseResult {
+    fn key(&self) -> (&str, Option<usize>) {
+        (&self.case, self.part)
+    }
+
+    fn error(case: &str, message: String) -> CaseResult {
+        CaseResult {
+            case: case.to_string(),
+            part: None,
+            outcome: Outcome::Error,
+            answer: Some(message),
+            expected: None,
+            elapsed_ns: 0,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.part {
+            Some(part) => format!("{} part {}", self.case, part),
+            None => self.case.clone(),
+        }
+    }
+
+    /// What the case came to, without its timing.
+    fn verdict(&self) -> String {
+        let answer = self.answer.as_deref().unwrap_or_default();
+        match (&self.outcome, &self.expected) {
+            (Outcome::Pass, _) => format!("pass {}", answer),
+            (Outcome::Fail, Some(expected)) => format!("FAIL {}, expected {}", answer, expected),
+            (Outcome::Timeout, _) => "TIMEOUT".to_string(),
+            (Outcome::Error, _) => format!("ERROR {}", answer),
+            _ => answer.to_string(),
+        }
+    }
+}
+
+/// Every `*.toml` fixture of a day, in name order.
+fn fixtures(directory: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn solve_case(
+    puzzle: &Puzzle,
+    case: &str,
+    input: &PuzzleInput,
+    params: &Params,
+    expected: &[(Part, Option<String>)],
+    deadline: Option<Duration>,
+) -> Vec<CaseResult> {
+    let parts: Vec<Part> = expected.iter().map(|(part, _)| *part).collect();
+    let results = match run::solve_within(puzzle, input, params, &parts, deadline) {
+        Ok(results) => results.parts,
+        Err(e) => return vec![CaseResult::error(case, e.to_string())],
+    };
+    results
+        .into_iter()
+        .zip(expected)
+        .map(|(result, (_, expected))| {
+            let (outcome, answer) = match result.status {
+                Status::Timeout => (Outcome::Timeout, None),
+                Status::Solved(answer) => {
+                    let answer = answer.to_string();
+                    let outcome = match expected {
+                        Some(expected) if *expected == answer => Outcome::Pass,
+                        Some(_) => Outcome::Fail,
+                        None => Outcome::Answered,
+                    };
+                    (outcome, Some(answer))
+                }
+            };
+            CaseResult {
+                case: case.to_string(),
+                part: Some(result.part.number()),
+                outcome,
+                answer,
+                expected: expected.clone(),
+                elapsed_ns: result.elapsed.as_nanos() as u64,
+            }
+        })
+        .collect()
+}
+
+/// Runs the parts each fixture in `directory` has answers for and, when
+/// given, both parts of the real input against the known answers.
+pub fn check(
+    puzzle: &Puzzle,
+    directory: &Path,
+    real: Option<(&PuzzleInput, &KnownAnswers)>,
+    deadline: Option<Duration>,
+) -> Vec<CaseResult> {
+    let mut results = Vec::new();
+    for path in fixtures(directory) {
+        let case = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let example = match fs::read_to_string(&path).map_err(|e| e.to_string()) {
+            Ok(text) => Example::parse(&text).map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        let example = match example {
+            Ok(example) if example.input.is_empty() => continue,
+            Ok(example) => example,
+            Err(e) => {
+                results.push(CaseResult::error(&case, e));
+                continue;
+            }
+        };
+        let expected: Vec<(Part, Option<String>)> = [(Part::One, &example.part1), (Part::Two, &example.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, Some(answer.clone()?))))
+            .collect();
+        let input = PuzzleInput {
+            name: path.display().to_string(),
+            text: example.input.clone(),
+        };
+        results.extend(solve_case(puzzle, &case, &input, &example.params(), &expected, deadline));
+    }
+
+    if let Some((input, known)) = real {
+        let entry = known.lookup(puzzle.day, &input_hash(&input.text));
+        let expected: Vec<(Part, Option<String>)> = Part::ALL
+            .iter()
+            .map(|&part| (part, entry.and_then(|entry| entry.expected(part)).map(str::to_string)))
+            .collect();
+        results.extend(solve_case(puzzle, "input", input, &Params::new(), &expected, deadline));
+    }
+    results
+}
+
+pub fn all_passed(results: &[CaseResult]) -> bool {
+    results
+        .iter()
+        .all(|result| matches!(result.outcome, Outcome::Pass | Outcome::Answered))
+}
+
+/// One line with the counts and total time, then a line for every case that
+/// didn't pass or changed since `previous`.
+pub fn summarize(title: &str, results: &[CaseResult], previous: Option<&[CaseResult]>) -> String {
+    let passed = results.iter().filter(|result| result.outcome == Outcome::Pass).count();
+    let failed = results
+        .iter()
+        .filter(|result| !matches!(result.outcome, Outcome::Pass | Outcome::Answered))
+        .count();
+    let total: u64 = results.iter().map(|result| result.elapsed_ns).sum();
+
+    let mut summary = format!("{}: {} passed, {} failed in {}", title, passed, failed, nanos(total));
+    if let Some(previous) = previous {
+        let before: u64 = previous.iter().map(|result| result.elapsed_ns).sum();
+        summary.push_str(&format!(" (was {})", nanos(before)));
+    }
+    let before: BTreeMap<_, _> = previous
+        .unwrap_or_default()
+        .iter()
+        .map(|result| (result.key(), result))
+        .collect();
+
+    for result in results {
+        let was = before.get(&result.key()).map(|previous| previous.verdict());
+        let verdict = result.verdict();
+        let changed = was.as_ref().is_some_and(|was| *was != verdict);
+        if result.outcome == Outcome::Pass && !changed {
+            continue;
+        }
+        summary.push_str(&format!("\n  {}: {}", result.label(), verdict));
+        if result.part.is_some() && result.outcome != Outcome::Timeout {
+            summary.push_str(&format!(" in {}", nanos(result.elapsed_ns)));
+        }
+        match was {
+            Some(was) if changed => summary.push_str(&format!(" (was {})", was)),
+            None if previous.is_some() => summary.push_str(" (new)"),
+            _ => {}
+        }
+    }
+    summary
+}
+
+/// Options `watch` hands on to every `check` it starts.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    pub real_input: bool,
+    pub timeout: Option<f64>,
+    pub offline: bool,
+    pub cache_dir: Option<PathBuf>,
+}
+
+fn check_command(root: &Path, puzzle: &Puzzle, options: &CheckOptions) -> Command {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command
+        .current_dir(root)
+        .args(["run", "--quiet", "-p", "aoc", "--", "check", "--json"])
+        .arg(puzzle.year.to_string())
+        .arg(puzzle.day.to_string());
+    if options.real_input {
+        command.arg("--input");
+    }
+    if let Some(timeout) = options.timeout {
+        command.arg(format!("--timeout={}", timeout));
+    }
+    if options.offline {
+        command.arg("--offline");
+    }
+    if let Some(cache_dir) = &options.cache_dir {
+        command.arg("--cache-dir").arg(cache_dir);
+    }
+    command.stdin(Stdio::null()).stderr(Stdio::inherit());
+    command
+}
+
+/// Builds and runs the check, returning `None` when it didn't get as far
+/// as printing results, e.g. because the day doesn't compile.
+fn run_check(root: &Path, puzzle: &Puzzle, options: &CheckOptions) -> Option<Vec<CaseResult>> {
+    let output = match check_command(root, puzzle, options).output() {
+        Ok(output) => output,
+        Err(e) => {
+            error!("can't start cargo: {}", e);
+            return None;
+        }
+    };
+    let results: Vec<CaseResult> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    (output.status.success() || !results.is_empty()).then_some(results)
+}
+
+fn is_relevant(event: &notify::Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event
+        .paths
+        .iter()
+        .any(|path| !path.components().any(|part| part.as_os_str() == "target"))
+}
+
+/// What `watch` follows: the day's crate, its fixtures, the shared code and,
+/// when given, the real input. An input that isn't cached yet is watched
+/// through its directory, so fetching it triggers a rerun too.
+fn watched_paths(
+    root: &Path,
+    puzzle: &Puzzle,
+    fixtures: PathBuf,
+    input: Option<&Path>,
+) -> Vec<(PathBuf, RecursiveMode)> {
+    let day = root
+        .join(puzzle.year.to_string())
+        .join(format!("d{}", puzzle.day))
+        .join("rust");
+    let mut watched = Vec::new();
+    if !fixtures.starts_with(&day) {
+        watched.push((fixtures, RecursiveMode::Recursive));
+    }
+    watched.push((day, RecursiveMode::Recursive));
+    watched.push((root.join("common").join("src"), RecursiveMode::Recursive));
+    if let Some(input) = input {
+        let path = match input.parent() {
+            Some(directory) if !input.exists() => directory,
+            _ => input,
+        };
+        watched.push((path.to_path_buf(), RecursiveMode::NonRecursive));
+    }
+    watched
+}
+
+/// Checks the day once, then again after every change to its crate, its
+/// fixtures, the shared code or the real `input`, until interrupted.
+pub fn watch(root: &Path, puzzle: &Puzzle, options: &CheckOptions, input: Option<&Path>) -> Result<(), RunError> {
+    let fixtures = aoc_common::fixtures_dir(puzzle.year, puzzle.day);
+    let watched = watched_paths(root, puzzle, fixtures, input);
+
+    let (sender, receiver) = mpsc::channel();
+    let watch_error = |e: notify::Error| RunError::Usage(format!("can't watch the sources: {}", e));
+    let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+    for (path, mode) in watched.iter() {
+        watcher.watch(path, *mode).map_err(watch_error)?;
+        info!("Watching {}", path.display());
+    }
+
+    let title = format!("{} day {}", puzzle.year, puzzle.day);
+    let mut previous: Option<Vec<CaseResult>> = None;
+    loop {
+        match run_check(root, puzzle, options) {
+            Some(results) => {
+                println!("{}", summarize(&title, &results, previous.as_deref()));
+                previous = Some(results);
+            }
+            None => println!("{}: build failed", title),
+        }
+
+        // wait for a change, then for the editor to finish saving
+        loop {
+            match receiver.recv() {
+                Ok(Ok(event)) if is_relevant(&event) => break,
+                Ok(Ok(_)) => continue,
+                Ok(Err(e)) => error!("watch error: {}", e),
+                Err(_) => return Ok(()),
+            }
+        }
+        while receiver.recv_timeout(SETTLE).is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    fn result(case: &str, part: usize, outcome: Outcome, answer: &str, elapsed_ns: u64) -> CaseResult {
+        CaseResult {
+            case: case.to_string(),
+            part: Some(part),
+            expected: (outcome == Outcome::Fail).then(|| "16".to_string()),
+            outcome,
+            answer: Some(answer.to_string()),
+            elapsed_ns,
+        }
+    }
+
+    #[test]
+    fn check_runs_the_committed_fixtures() {
+        let puzzle = registry::find(2024, 3).unwrap();
+        let directory = aoc_common::fixtures_dir(2024, 3);
+
+        let results = check(&puzzle, &directory, None, None);
+        let cases: Vec<_> = results.iter().map(|result| (result.label(), result.outcome.clone())).collect();
+        assert_eq!(
+            cases,
+            vec![
+                ("example1 part 1".to_string(), Outcome::Pass),
+                ("example2 part 2".to_string(), Outcome::Pass)
+            ]
+        );
+        assert!(all_passed(&results));
+    }
+
+    #[test]
+    fn check_compares_real_input_with_known_answers() {
+        let puzzle = registry::find(2024, 1).unwrap();
+        let input = PuzzleInput {
+            name: "input.txt".to_string(),
+            text: "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n".to_string(),
+        };
+        let known = KnownAnswers::parse("[day1]\npart1 = 11\n").unwrap();
+        let empty = std::env::temp_dir().join("aoc-watch-no-fixtures");
+
+        let results = check(&puzzle, &empty, Some((&input, &known)), None);
+        assert_eq!(results[0].outcome, Outcome::Pass);
+        assert_eq!((results[1].outcome.clone(), results[1].answer.as_deref()), (Outcome::Answered, Some("31")));
+    }
+
+    #[test]
+    fn watch_follows_the_fixtures_and_real_input() {
+        let puzzle = registry::find(2024, 3).unwrap();
+        let root = Path::new("/repo");
+        let day = root.join("2024/d3/rust");
+        let cached = aoc_common::repository_root().join("Cargo.toml");
+        let missing = Path::new("/cache/2024/d3/input.txt");
+
+        let watched = watched_paths(root, &puzzle, day.join("fixtures"), Some(&cached));
+        let paths: Vec<&Path> = watched.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(paths, vec![day.as_path(), &root.join("common/src"), &cached]);
+        assert_eq!(watched[2].1, RecursiveMode::NonRecursive);
+
+        let watched = watched_paths(root, &puzzle, PathBuf::from("/elsewhere/fixtures"), Some(missing));
+        let paths: Vec<&Path> = watched.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("/elsewhere/fixtures"),
+                &day,
+                &root.join("common/src"),
+                Path::new("/cache/2024/d3")
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_lists_failures_and_changes_since_last_run() {
+        let previous = vec![
+            result("example1", 1, Outcome::Pass, "6", 1_000),
+            result("example1", 2, Outcome::Pass, "16", 2_000),
+        ];
+        let current = vec![
+            result("example1", 1, Outcome::Pass, "6", 1_500),
+            result("example1", 2, Outcome::Fail, "6", 3_000),
+            result("input", 1, Outcome::Answered, "240", 20_000),
+        ];
+
+        assert_eq!(
+            summarize("2024 day 19", &current, Some(&previous)),
+            "2024 day 19: 1 passed, 1 failed in 24.5µs (was 3000ns)\n  \
+             example1 part 2: FAIL 6, expected 16 in 3000ns (was pass 16)\n  \
+             input part 1: 240 in 20.0µs (new)"
+        );
+        assert_eq!(
+            summarize("2024 day 19", &previous, None),
+            "2024 day 19: 2 passed, 0 failed in 3000ns"
+        );
+    }
+
+    #[test]
+    fn results_survive_the_json_lines_between_runs() {
+        let result = result("example1", 2, Outcome::Fail, "6", 3_000);
+        let line = serde_json::to_string(&result).unwrap();
+        assert_eq!(serde_json::from_str::<CaseResult>(&line).unwrap(), result);
+    }
+}
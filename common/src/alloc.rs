@@ -0,0 +1,219 @@
+//! Accounting of heap allocations, to see which phase of a day allocates.
+//!
+//! Counting is opt-in: a binary installs `CountingAllocator` as its global
+//! allocator (the runner does with its `count-allocs` feature), and until
+//! then `measure` has nothing to report. Every thread keeps its own counts,
+//! so phases running side by side don't see each other's allocations; what
+//! a phase allocates on threads it starts itself isn't counted either.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// The system allocator, counting what goes through it.
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// One thread's counts. `live` goes negative when the thread frees memory
+/// another one allocated.
+#[derive(Debug, Clone, Copy)]
+struct Counts {
+    allocations: u64,
+    allocated: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    // const and without a destructor, so the allocator may use it at any time
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            allocated: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counts)) {
+    // a thread being torn down has no counts left to update
+    let _ = COUNTS.try_with(|counts| {
+        let mut current = counts.get();
+        f(&mut current);
+        counts.set(current);
+    });
+}
+
+fn allocated(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    update(|counts| {
+        counts.allocations += 1;
+        counts.allocated += size as u64;
+        counts.live += size as i64;
+        counts.peak = counts.peak.max(counts.live);
+    });
+}
+
+fn freed(size: usize) {
+    update(|counts| counts.live -= size as i64);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    // a reallocation counts as a new allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// What one phase allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Bytes allocated in total, including what was freed again.
+    pub bytes: u64,
+    /// Most bytes live at once, above what was live when the phase began.
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>9} allocations  {:>9} allocated  peak {:>9}",
+            self.allocations,
+            bytes(self.bytes),
+            bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Whether `CountingAllocator` is the global allocator.
+pub fn is_counting() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, and reports what it allocated on this thread when allocations
+/// are counted. Phases are not expected to nest: the inner one resets the
+/// peak.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !is_counting() {
+        return (f(), None);
+    }
+    update(|counts| counts.peak = counts.live);
+    let before = COUNTS.with(Cell::get);
+
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.allocated - before.allocated,
+        peak_bytes: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+/// Formats a byte count with a binary unit that keeps two to four digits.
+pub fn bytes(bytes: u64) -> String {
+    const KIB: u64 = 1 << 10;
+    const MIB: u64 = 1 << 20;
+    const GIB: u64 = 1 << 30;
+    const TEN_KIB: u64 = 10 * KIB;
+    const TEN_MIB: u64 = 10 * MIB;
+    const TEN_GIB: u64 = 10 * GIB;
+    match bytes {
+        0..TEN_KIB => format!("{}B", bytes),
+        TEN_KIB..TEN_MIB => format!("{:.1}KiB", bytes as f64 / KIB as f64),
+        TEN_MIB..TEN_GIB => format!("{:.1}MiB", bytes as f64 / MIB as f64),
+        _ => format!("{:.2}GiB", bytes as f64 / GIB as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn measure_counts_what_the_phase_allocates() {
+        let (length, stats) = measure(|| {
+            let buffer = vec![1u8; 1 << 20];
+            let copies: Vec<Vec<u8>> = (0..3).map(|_| buffer.clone()).collect();
+            copies.len()
+        });
+        let stats = stats.expect("the test allocator counts");
+        assert_eq!(length, 3);
+        assert_eq!(stats.allocations, 5);
+        assert!(stats.bytes >= 4 << 20, "{:?}", stats);
+        assert!(stats.peak_bytes >= 4 << 20, "{:?}", stats);
+    }
+
+    #[test]
+    fn phases_on_other_threads_are_not_counted() {
+        let barrier = Barrier::new(2);
+        let (busy, quiet) = thread::scope(|scope| {
+            let busy = scope.spawn(|| {
+                measure(|| {
+                    barrier.wait();
+                    let buffer = vec![1u8; 4 << 20];
+                    barrier.wait();
+                    buffer.len()
+                })
+            });
+            let quiet = scope.spawn(|| {
+                measure(|| {
+                    barrier.wait();
+                    barrier.wait();
+                })
+            });
+            (busy.join().unwrap(), quiet.join().unwrap())
+        });
+
+        let busy = busy.1.expect("the test allocator counts");
+        assert_eq!((busy.allocations, busy.peak_bytes), (1, 4 << 20));
+        assert_eq!(quiet.1, Some(AllocStats::default()));
+    }
+
+    #[test]
+    fn bytes_picks_a_readable_unit() {
+        assert_eq!(bytes(9_000), "9000B");
+        assert_eq!(bytes(12_800), "12.5KiB");
+        assert_eq!(bytes(3 << 20), "3072.0KiB");
+        assert_eq!(bytes(12 << 20), "12.0MiB");
+        assert_eq!(bytes(20 << 30), "20.00GiB");
+    }
+}
@@ -0,0 +1,226 @@
+//! Confirmed answers for the real puzzle inputs, one file per year.
+//!
+//! `YEAR/answers.toml` has a table per day. An entry may name the SHA-256 of
+//! the input it belongs to, so answers for several accounts can live side by
+//! side; an entry without `input` matches any input.
+//!
+//! ```toml
+//! [day1]
+//! part1 = 1882714
+//! part2 = 19437052
+//!
+//! [[day18]]
+//! input = "5d41402abc4b2a76b9719d911017c592..."
+//! part1 = 250
+//! part2 = "56,8"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::error::ParseError;
+use crate::input::repository_root;
+use crate::puzzle::Part;
+
+/// Where the known answers of a year are kept.
+pub fn answers_path(year: u32) -> PathBuf {
+    repository_root().join(year.to_string()).join("answers.toml")
+}
+
+/// Hex SHA-256 of an input, the same as `sha256sum input.txt` prints.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[derive(Debug, Error)]
+pub enum AnswersError {
+    #[error("can't read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// The answers confirmed for one input of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownAnswer {
+    /// `input_hash` of the input these answers belong to.
+    pub input: Option<String>,
+    #[serde(default, deserialize_with = "answer_text")]
+    pub part1: Option<String>,
+    #[serde(default, deserialize_with = "answer_text")]
+    pub part2: Option<String>,
+}
+
+impl KnownAnswer {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Answers are written as TOML integers or, for text and numbers that don't
+/// fit in an `i64`, as strings.
+pub(crate) fn answer_text<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(i64),
+        Text(String),
+    }
+
+    Ok(Some(match Raw::deserialize(deserializer)? {
+        Raw::Number(number) => number.to_string(),
+        Raw::Text(text) => text,
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DayEntries {
+    One(KnownAnswer),
+    Many(Vec<KnownAnswer>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    days: BTreeMap<u32, Vec<KnownAnswer>>,
+}
+
+impl KnownAnswers {
+    /// Loads a year's answers; a missing file just means nothing is known yet.
+    pub fn load(path: &Path) -> Result<KnownAnswers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(KnownAnswers::parse(&text).map_err(|e| e.with_file(path))?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(source) => Err(AnswersError::Io {
+                path: path.display().to_string(),
+                source,
+            }),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<KnownAnswers, ParseError> {
+        let tables: BTreeMap<String, DayEntries> = toml::from_str(text).map_err(|e| {
+            let error = ParseError::new(e.message().to_string());
+            match e.span() {
+                Some(span) => {
+                    let before = &text[..span.start];
+                    let line = before.matches('\n').count() + 1;
+                    let column = before.len() - before.rfind('\n').map_or(0, |at| at + 1) + 1;
+                    error.at(line, column)
+                }
+                None => error,
+            }
+        })?;
+
+        let mut days = BTreeMap::new();
+        for (key, entries) in tables {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| ParseError::new(format!("expected a table named dayN, found `{}`", key)))?;
+            let entries = match entries {
+                DayEntries::One(entry) => vec![entry],
+                DayEntries::Many(entries) => entries,
+            };
+            days.insert(day, entries);
+        }
+        Ok(KnownAnswers { days })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.days.keys().copied()
+    }
+
+    /// The answers recorded for exactly this input, or else the ones that
+    /// don't name an input.
+    pub fn lookup(&self, day: u32, input_hash: &str) -> Option<&KnownAnswer> {
+        let entries = self.days.get(&day)?;
+        entries
+            .iter()
+            .find(|entry| entry.input.as_deref() == Some(input_hash))
+            .or_else(|| entries.iter().find(|entry| entry.input.is_none()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[day1]
+part1 = 11
+part2 = 31
+
+[[day18]]
+input = "aaaa"
+part1 = 22
+
+[[day18]]
+input = "bbbb"
+part1 = 146
+part2 = "6,1"
+"#;
+
+    #[test]
+    fn parse_reads_single_and_hashed_entries() {
+        let known = KnownAnswers::parse(ANSWERS).unwrap();
+        assert_eq!(known.days().collect::<Vec<_>>(), vec![1, 18]);
+
+        let day1 = known.lookup(1, "anything").unwrap();
+        assert_eq!(day1.expected(Part::One), Some("11"));
+        assert_eq!(day1.expected(Part::Two), Some("31"));
+
+        let day18 = known.lookup(18, "bbbb").unwrap();
+        assert_eq!(day18.expected(Part::Two), Some("6,1"));
+        assert_eq!(known.lookup(18, "aaaa").unwrap().expected(Part::Two), None);
+        assert_eq!(known.lookup(18, "cccc"), None);
+        assert_eq!(known.lookup(4, "aaaa"), None);
+    }
+
+    #[test]
+    fn parse_reports_position_of_bad_toml() {
+        let error = KnownAnswers::parse("[day1]\npart1 = 11\npart2 = = 4\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(3), Some(9)));
+
+        let error = KnownAnswers::parse("[first]\npart1 = 1\n").unwrap_err();
+        assert_eq!(error.to_string(), "expected a table named dayN, found `first`");
+    }
+
+    #[test]
+    fn load_treats_missing_file_as_empty() {
+        let known = KnownAnswers::load(Path::new("./d0/answers.toml")).unwrap();
+        assert!(known.is_empty());
+    }
+
+    #[test]
+    fn input_hash_matches_sha256sum() {
+        assert_eq!(
+            input_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
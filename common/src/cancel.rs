@@ -0,0 +1,82 @@
+//! Cooperative cancellation of a running part.
+//!
+//! The runner solves each part on its own thread under a `CancelToken` and
+//! cancels it when the part runs out of time. Long loops poll `cancelled()`
+//! and bail out with whatever they have; the runner throws that away.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<CancelToken> = RefCell::new(CancelToken::new());
+}
+
+/// Runs `f` with `token` as the current thread's token.
+pub fn with_token<R>(token: &CancelToken, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(token.clone()));
+    let result = f();
+    CURRENT.with(|current| current.replace(previous));
+    result
+}
+
+/// Whether the part running on this thread should stop. Outside the runner
+/// nothing ever cancels, so this is always false in tests.
+pub fn cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().is_cancelled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_follows_the_installed_token() {
+        let token = CancelToken::new();
+        assert!(!cancelled());
+
+        with_token(&token, || {
+            assert!(!cancelled());
+            token.cancel();
+            assert!(cancelled());
+        });
+        assert!(!cancelled());
+    }
+
+    #[test]
+    fn cancelling_reaches_other_threads() {
+        let token = CancelToken::new();
+        let worker = {
+            let token = token.clone();
+            std::thread::spawn(move || {
+                with_token(&token, || {
+                    let mut spins: u64 = 0;
+                    while !cancelled() {
+                        spins += 1;
+                        std::hint::spin_loop();
+                    }
+                    spins
+                })
+            })
+        };
+        token.cancel();
+        worker.join().unwrap();
+    }
+}
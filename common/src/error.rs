@@ -0,0 +1,119 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+/// Raised when a puzzle input can't be turned into a day's `Input`.
+///
+/// Parsers fill in the line and column (both 1-based) and what they expected
+/// there; whoever read the file adds its path.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{}{message}", Location(self))]
+pub struct ParseError {
+    message: String,
+    file: Option<PathBuf>,
+    line: Option<usize>,
+    column: Option<usize>,
+    expected: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ParseError {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            expected: None,
+        }
+    }
+
+    /// `expected` was wanted at `line`:`column`, but `found` was there.
+    pub fn expected(line: usize, column: usize, expected: impl Into<String>, found: &str) -> Self {
+        let expected = expected.into();
+        let message = if found.is_empty() {
+            format!("expected {}, found nothing", expected)
+        } else {
+            format!("expected {}, found `{}`", expected, found)
+        };
+        ParseError {
+            expected: Some(expected),
+            ..ParseError::new(message).at(line, column)
+        }
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn expected_token(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+}
+
+struct Location<'a>(&'a ParseError);
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.0.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let (Some(line), Some(column)) = (self.0.line, self.0.column) {
+            write!(f, "{}:{}:", line, column)?;
+        }
+        if self.0.file.is_some() || self.0.line.is_some() {
+            write!(f, " ")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_without_location_is_the_message() {
+        assert_eq!(ParseError::new("empty input").to_string(), "empty input");
+    }
+
+    #[test]
+    fn display_with_file_line_and_column() {
+        let error = ParseError::expected(3, 5, "page number", "x").with_file("d5/input.txt");
+        assert_eq!(
+            error.to_string(),
+            "d5/input.txt:3:5: expected page number, found `x`"
+        );
+        assert_eq!(error.expected_token(), Some("page number"));
+        assert_eq!((error.line(), error.column()), (Some(3), Some(5)));
+    }
+
+    #[test]
+    fn display_for_missing_token() {
+        let error = ParseError::expected(7, 1, "Button B line", "");
+        assert_eq!(error.to_string(), "7:1: expected Button B line, found nothing");
+    }
+}
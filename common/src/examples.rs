@@ -0,0 +1,393 @@
+//! Puzzle examples as test fixtures.
+//!
+//! `extract` pulls the example input and the highlighted answers out of a
+//! puzzle description page. Each example is kept as a TOML file in the day
+//! crate's `fixtures` directory:
+//!
+//! ```toml
+//! part1 = 12
+//! params = { wide = 11, tall = 7 }
+//! input = '''
+//! p=0,4 v=3,-3
+//! '''
+//! ```
+//!
+//! and `example_tests!` turns every listed fixture into a test of the day's
+//! `Solution`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::answers::answer_text;
+use crate::input::repository_root;
+use crate::params::Params;
+use crate::solution::{Answer, Solution};
+
+/// Where the example fixtures of a day's crate live.
+pub fn fixtures_dir(year: u32, day: u32) -> PathBuf {
+    repository_root()
+        .join(year.to_string())
+        .join(format!("d{}", day))
+        .join("rust")
+        .join("fixtures")
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Example {
+    #[serde(default, deserialize_with = "answer_text")]
+    pub part1: Option<String>,
+    #[serde(default, deserialize_with = "answer_text")]
+    pub part2: Option<String>,
+    /// Puzzle parameters the example needs, such as day 14's smaller space.
+    #[serde(default, deserialize_with = "param_values")]
+    pub params: BTreeMap<String, String>,
+    #[serde(default)]
+    pub input: String,
+}
+
+fn param_values<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let values = BTreeMap::<String, toml::Value>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .map(|(name, value)| match value {
+            toml::Value::String(text) => (name, text),
+            value => (name, value.to_string()),
+        })
+        .collect())
+}
+
+impl Example {
+    pub fn parse(text: &str) -> Result<Example, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+            .iter()
+            .fold(Params::new(), |params, (name, value)| params.with(name, value))
+    }
+
+    /// Writes the fixture, keeping the input verbatim in a literal string
+    /// when it can be one.
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        for (key, answer) in [("part1", &self.part1), ("part2", &self.part2)] {
+            if let Some(answer) = answer {
+                match answer.parse::<i64>() {
+                    Ok(number) if number.to_string() == *answer => writeln!(out, "{} = {}", key, number).unwrap(),
+                    _ => writeln!(out, "{} = {}", key, toml_string(answer)).unwrap(),
+                }
+            }
+        }
+        if !self.params.is_empty() {
+            let params: Vec<String> = self
+                .params
+                .iter()
+                .map(|(name, value)| format!("{} = {}", toml_key(name), toml_string(value)))
+                .collect();
+            writeln!(out, "params = {{ {} }}", params.join(", ")).unwrap();
+        }
+        // TOML drops the newline right after the opening `'''`, and the
+        // closing one has to start a line, so only inputs that end in a
+        // newline and don't start with one survive the literal form.
+        let literal = !self.input.contains("'''")
+            && !self.input.chars().any(|ch| ch.is_control() && ch != '\n' && ch != '\t')
+            && !self.input.starts_with('\n')
+            && (self.input.is_empty() || self.input.ends_with('\n'));
+        if !literal {
+            writeln!(out, "input = {}", toml_string(&self.input)).unwrap();
+            return out;
+        }
+        writeln!(out, "input = '''").unwrap();
+        out.push_str(&self.input);
+        out.push_str("'''\n");
+        out
+    }
+}
+
+/// `text` as a TOML string, escaped as needed.
+fn toml_string(text: &str) -> String {
+    toml::Value::String(text.to_string()).to_string()
+}
+
+fn toml_key(name: &str) -> String {
+    let bare = !name.is_empty() && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    if bare {
+        name.to_string()
+    } else {
+        toml_string(name)
+    }
+}
+
+/// Finds the examples on a puzzle description page.
+///
+/// Every part's `<article>` contributes the first `<pre><code>` block as the
+/// example input and the last highlighted `<code><em>` as its answer. When
+/// part two has no block of its own, its answer goes with part one's example.
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (part, article) in articles(page).into_iter().take(2).enumerate() {
+        let input = first_between(article, "<pre><code>", "</code></pre>").map(|block| decode(&strip_tags(block)));
+        let answer = last_answer(article);
+
+        let example = match input {
+            Some(input) => match examples.iter_mut().position(|example| example.input == input) {
+                Some(index) => &mut examples[index],
+                None => {
+                    examples.push(Example {
+                        input,
+                        ..Example::default()
+                    });
+                    examples.last_mut().unwrap()
+                }
+            },
+            None => match examples.last_mut() {
+                Some(example) => example,
+                None => continue,
+            },
+        };
+        if part == 0 {
+            example.part1 = answer;
+        } else {
+            example.part2 = answer;
+        }
+    }
+    examples
+}
+
+fn articles(page: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else { break };
+        found.push(&rest[start..start + end]);
+        rest = &rest[start + end..];
+    }
+    found
+}
+
+fn first_between<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = text.find(open)? + open.len();
+    let end = text[start..].find(close)?;
+    Some(&text[start..start + end])
+}
+
+fn last_answer(article: &str) -> Option<String> {
+    ["<code><em>", "<em><code>"]
+        .iter()
+        .filter_map(|open| {
+            let close = if open.starts_with("<code>") { "</em></code>" } else { "</code></em>" };
+            let start = article.rfind(open)?;
+            let answer = first_between(&article[start..], open, close)?;
+            Some((start, decode(&strip_tags(answer))))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| answer)
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Runs one fixture against a day's solution, panicking on any difference.
+/// Parts the solution leaves `Unsolved` are skipped.
+pub fn check<S: Solution>(name: &str, fixture: &str) {
+    let example = Example::parse(fixture).unwrap_or_else(|e| panic!("fixture {}: {}", name, e));
+    if example.input.is_empty() {
+        // a placeholder waiting for its example
+        return;
+    }
+    let input = S::parse_with(&example.input, &example.params()).unwrap_or_else(|e| panic!("{}: {}", name, e));
+    let compare = |part: usize, expected: &Option<String>, solve: fn(&S::Input) -> Answer| {
+        let Some(expected) = expected else { return };
+        match solve(&input) {
+            // a part not written yet, as in a freshly scaffolded day
+            Answer::Unsolved => {}
+            answer => assert_eq!(&answer.to_string(), expected, "{} part {}", name, part),
+        }
+    };
+    compare(1, &example.part1, S::part1);
+    compare(2, &example.part2, S::part2);
+}
+
+/// One test per fixture, named after the file in the crate's `fixtures`
+/// directory: `example_tests!(Day14: example1, example2);`
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ident: $($name:ident),+ $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            $(
+                #[test]
+                fn $name() {
+                    $crate::examples::check::<$solution>(
+                        stringify!($name),
+                        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/", stringify!($name), ".toml")),
+                    );
+                }
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+    use crate::ParseError;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>Maybe the lists are only off by a small amount! To find out, pair up the numbers, like <code>1 &lt; 2</code>.</p>
+<p>In the example above, this is <code>2 + 1</code>, a total distance of <code><em>11</em></code>!</p>
+</article>
+<p>Your puzzle answer was <code>1882714</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>So, for these example lists, the similarity score at the end of this process is <em><code>31</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extract_reuses_part_one_example_for_part_two() {
+        let examples = extract(PAGE);
+        assert_eq!(
+            examples,
+            vec![Example {
+                part1: Some("11".to_string()),
+                part2: Some("31".to_string()),
+                params: BTreeMap::new(),
+                input: "3   4\n4   3\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn extract_keeps_separate_part_two_example() {
+        let page = r#"<article><pre><code>xmul(2,4)&amp;mul[3,7]</code></pre><code><em>161</em></code></article>
+<article><pre><code>don't()<em>mul(1,1)</em></code></pre><p><code><em>48</em></code></p></article>"#;
+        let examples = extract(page);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "xmul(2,4)&mul[3,7]");
+        assert_eq!(examples[0].part1.as_deref(), Some("161"));
+        assert_eq!(examples[1].input, "don't()mul(1,1)");
+        assert_eq!((examples[1].part1.as_deref(), examples[1].part2.as_deref()), (None, Some("48")));
+    }
+
+    #[test]
+    fn fixture_round_trips_through_toml() {
+        let example = Example {
+            part1: Some("12".to_string()),
+            part2: Some("6,1".to_string()),
+            params: BTreeMap::from([("wide".to_string(), "11".to_string())]),
+            input: "p=0,4 v=3,-3\n".to_string(),
+        };
+        let text = example.to_toml();
+        assert_eq!(
+            text,
+            "part1 = 12\npart2 = \"6,1\"\nparams = { wide = \"11\" }\ninput = '''\np=0,4 v=3,-3\n'''\n"
+        );
+        assert_eq!(Example::parse(&text).unwrap(), example);
+    }
+
+    #[test]
+    fn fixture_escapes_what_a_literal_cannot_hold() {
+        let example = Example {
+            part1: Some("007".to_string()),
+            part2: Some("tab\there \"quoted\"\u{1}".to_string()),
+            params: BTreeMap::from([("odd name".to_string(), "a\nb".to_string())]),
+            input: "one ''' two\r\n".to_string(),
+        };
+        let text = example.to_toml();
+        assert!(text.starts_with("part1 = \"007\"\n"), "{}", text);
+        assert!(!text.contains("input = '''"), "{}", text);
+        assert_eq!(Example::parse(&text).unwrap(), example);
+    }
+
+    #[test]
+    fn fixture_keeps_inputs_a_literal_would_change() {
+        for input in ["xmul(2,4)&mul[3,7]", "\nstarts blank\n", "\n", ""] {
+            let example = Example {
+                input: input.to_string(),
+                ..Example::default()
+            };
+            let text = example.to_toml();
+            assert_eq!(Example::parse(&text).unwrap(), example, "{}", text);
+        }
+        let text = Example {
+            input: "xmul(2,4)".to_string(),
+            ..Example::default()
+        }
+        .to_toml();
+        assert_eq!(text, "input = \"xmul(2,4)\"\n");
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        const PARAMS: &'static [&'static str] = &["times"];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Self::parse_with(input, &Params::new())
+        }
+
+        fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+            let times: i64 = params.get("times")?.unwrap_or(1);
+            Ok(input.split_whitespace().map(|n| n.parse::<i64>().unwrap() * times).collect())
+        }
+
+        fn part1(numbers: &Self::Input) -> Answer {
+            numbers.iter().sum::<i64>().into()
+        }
+    }
+
+    #[test]
+    fn check_passes_params_and_compares_answers() {
+        check::<Sum>("sum", "part1 = 12\nparams = { times = 2 }\ninput = '''\n1 2 3\n'''\n");
+        check::<Sum>("placeholder", "input = ''\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "sum part 1")]
+    fn check_reports_wrong_answer() {
+        check::<Sum>("sum", "part1 = 7\ninput = '''\n1 2 3\n'''\n");
+    }
+
+    #[test]
+    fn check_skips_unsolved_parts() {
+        check::<Sum>("sum", "part1 = 6\npart2 = 6\ninput = '''\n1 2 3\n'''\n");
+    }
+}
@@ -0,0 +1,323 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::input::{puzzle_input_path, repository_root};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/btomala/AdventOfCode input fetcher";
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("no {what} for {year} day {day} at {path} and {reason}")]
+    NotCached {
+        what: &'static str,
+        year: u32,
+        day: u32,
+        path: String,
+        reason: &'static str,
+    },
+    #[error("can't download {url}: {message}")]
+    Download { url: String, message: String },
+    #[error("can't access {path}: {source}")]
+    Cache {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// A day's input and the file it was read from, for error messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedInput {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+/// Finds puzzle inputs in the cache and downloads the missing ones.
+///
+/// Inputs never change, so a cached file always wins unless `refresh` asks
+/// for a new copy; when that download fails the cached copy is used anyway.
+#[derive(Debug, Clone)]
+pub struct InputProvider {
+    pub(crate) base_url: String,
+    pub(crate) session: Option<String>,
+    cache_dir: PathBuf,
+    offline: bool,
+    refresh: bool,
+}
+
+impl Default for InputProvider {
+    fn default() -> Self {
+        InputProvider {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            // where the `YEAR/dN/input.txt` files always lived
+            cache_dir: repository_root(),
+            offline: false,
+            refresh: false,
+        }
+    }
+}
+
+impl InputProvider {
+    /// Reads `AOC_SESSION`, `AOC_CACHE_DIR`, `AOC_BASE_URL` and `AOC_OFFLINE`.
+    pub fn from_env() -> Self {
+        let mut provider = InputProvider::default();
+        if let Ok(session) = env::var("AOC_SESSION") {
+            provider = provider.session(session);
+        }
+        if let Ok(cache_dir) = env::var("AOC_CACHE_DIR") {
+            provider = provider.cache_dir(cache_dir);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            provider = provider.base_url(base_url);
+        }
+        if env::var("AOC_OFFLINE").is_ok_and(|value| !value.is_empty() && value != "0") {
+            provider = provider.offline(true);
+        }
+        provider
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Value of the `session` cookie of a logged in browser.
+    pub fn session(mut self, session: impl Into<String>) -> Self {
+        let session = session.into().trim().to_string();
+        self.session = (!session.is_empty()).then_some(session);
+        self
+    }
+
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Never touch the network, only read the cache.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Download again even when the input is cached.
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Where the input of a day is cached.
+    pub fn cache_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_dir.join(puzzle_input_path(year, day))
+    }
+
+    /// Where the puzzle description of a day is cached.
+    pub fn page_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_path(year, day).with_file_name("puzzle.html")
+    }
+
+    pub fn fetch(&self, year: u32, day: u32) -> Result<FetchedInput, FetchError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.fetch_cached("input", year, day, self.cache_path(year, day), url)
+    }
+
+    /// The puzzle description page. Part two only shows up on it once part
+    /// one is solved, so `refresh` is the way to pick it up.
+    pub fn fetch_page(&self, year: u32, day: u32) -> Result<FetchedInput, FetchError> {
+        let url = format!("{}/{}/day/{}", self.base_url, year, day);
+        self.fetch_cached("puzzle page", year, day, self.page_path(year, day), url)
+    }
+
+    fn fetch_cached(
+        &self,
+        what: &'static str,
+        year: u32,
+        day: u32,
+        path: PathBuf,
+        url: String,
+    ) -> Result<FetchedInput, FetchError> {
+        let cached = path.is_file();
+        if cached && !self.refresh {
+            return read_cached(path);
+        }
+
+        let reason = if self.offline {
+            Some("running offline")
+        } else if self.session.is_none() {
+            Some("AOC_SESSION is not set")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return if cached {
+                read_cached(path)
+            } else {
+                Err(FetchError::NotCached {
+                    what,
+                    year,
+                    day,
+                    path: path.display().to_string(),
+                    reason,
+                })
+            };
+        }
+
+        match self.download(url) {
+            Ok(text) => {
+                store(&path, &text)?;
+                info!("Downloaded {} day {} {} to {}", year, day, what, path.display());
+                Ok(FetchedInput { path, text })
+            }
+            Err(e) if cached => {
+                warn!("{}; using the cached copy", e);
+                read_cached(path)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn download(&self, url: String) -> Result<String, FetchError> {
+        let session = self.session.as_deref().unwrap_or_default();
+        let failed = |message: String| FetchError::Download {
+            url: url.clone(),
+            message,
+        };
+
+        let response = agent()
+            .get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(400, _) => failed("the session cookie was rejected".to_string()),
+                ureq::Error::Status(404, _) => failed("the puzzle is not unlocked yet".to_string()),
+                e => failed(e.to_string()),
+            })?;
+        response.into_string().map_err(|e| failed(e.to_string()))
+    }
+}
+
+/// HTTP client with the timeout and user agent the website asks for.
+pub(crate) fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+fn read_cached(path: PathBuf) -> Result<FetchedInput, FetchError> {
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(FetchedInput { path, text }),
+        Err(source) => Err(FetchError::Cache {
+            path: path.display().to_string(),
+            source,
+        }),
+    }
+}
+
+fn store(path: &Path, text: &str) -> Result<(), FetchError> {
+    let cache_error = |source| FetchError::Cache {
+        path: path.display().to_string(),
+        source,
+    };
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(cache_error)?;
+    }
+    fs::write(path, text).map_err(cache_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn canned_inputs() -> MockServer {
+        MockServer::start(|request| match request.header("Cookie") {
+            Some("session=secret") if request.path == "/2024/day/1/input" => {
+                (200, "3   4\n4   3\n".to_string())
+            }
+            Some("session=secret") => (404, "Please don't repeatedly request this endpoint".to_string()),
+            _ => (400, "Puzzle inputs differ by user.".to_string()),
+        })
+    }
+
+    #[test]
+    fn downloads_once_and_then_reads_the_cache() {
+        let server = canned_inputs();
+        let cache = temp_cache("download");
+        let provider = InputProvider::default()
+            .base_url(server.url())
+            .session("secret")
+            .cache_dir(&cache);
+
+        let input = provider.fetch(2024, 1).unwrap();
+        assert_eq!(input.text, "3   4\n4   3\n");
+        assert_eq!(input.path, cache.join("2024/d1/input.txt"));
+        assert_eq!(fs::read_to_string(&input.path).unwrap(), input.text);
+
+        assert_eq!(provider.fetch(2024, 1).unwrap(), input);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/2024/day/1/input");
+        assert_eq!(requests[0].header("User-Agent"), Some(USER_AGENT));
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn reports_rejected_session_and_locked_days() {
+        let server = canned_inputs();
+        let cache = temp_cache("rejected");
+        let provider = InputProvider::default().base_url(server.url()).cache_dir(&cache);
+
+        let error = provider.clone().session("stale").fetch(2024, 1).unwrap_err();
+        assert!(error.to_string().ends_with("the session cookie was rejected"), "{}", error);
+
+        let error = provider.session("secret").fetch(2024, 25).unwrap_err();
+        assert!(error.to_string().ends_with("the puzzle is not unlocked yet"), "{}", error);
+        assert!(!cache.exists());
+    }
+
+    #[test]
+    fn falls_back_to_the_cache_when_offline_or_unreachable() {
+        let cache = temp_cache("offline");
+        let provider = InputProvider::default()
+            // nothing listens on the discard port
+            .base_url("http://127.0.0.1:9")
+            .session("secret")
+            .cache_dir(&cache);
+
+        let error = provider.clone().offline(true).fetch(2024, 1).unwrap_err();
+        assert!(matches!(error, FetchError::NotCached { reason: "running offline", .. }));
+
+        store(&provider.cache_path(2024, 1), "1   2\n").unwrap();
+        assert_eq!(provider.clone().offline(true).fetch(2024, 1).unwrap().text, "1   2\n");
+        assert_eq!(provider.refresh(true).fetch(2024, 1).unwrap().text, "1   2\n");
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn needs_a_session_to_download() {
+        let cache = temp_cache("anonymous");
+        let error = InputProvider::default().cache_dir(&cache).fetch(2024, 1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "no input for 2024 day 1 at {} and AOC_SESSION is not set",
+                cache.join("2024/d1/input.txt").display()
+            )
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+use crate::parse::numbered_lines;
+use crate::point::Point;
+
+/// A rectangular grid stored row by row in one flat buffer.
+///
+/// Cells are addressed by `(x, y)` with `x` running along a row and `y` down
+/// the rows. `get` and friends are bounds checked; indexing with `grid[(x, y)]`
+/// panics outside the grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from rows of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ParseError::new(format!(
+                    "row {} has {} cells, expected {}",
+                    y + 1,
+                    row.len(),
+                    width
+                )));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses one row per line, turning every character into a cell with
+    /// `cell`. Blank lines are ignored.
+    pub fn parse<F>(input: &str, expected: &str, cell: F) -> Result<Self, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (line_number, line) in numbered_lines(input) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut row_width = 0;
+            for (column, ch) in line.chars().enumerate() {
+                match cell(ch) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(ParseError::expected(
+                            line_number,
+                            column + 1,
+                            expected,
+                            &ch.to_string(),
+                        ))
+                    }
+                }
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::expected(
+                        line_number,
+                        row_width.min(width) + 1,
+                        format!("a row of {} cells", width),
+                        line,
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Like `contains`, for coordinates that may have stepped off the edge.
+    pub fn contains_signed(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && self.contains(x as usize, y as usize)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_signed(&self, x: i64, y: i64) -> Option<&T> {
+        if self.contains_signed(x, y) {
+            self.get(x as usize, y as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Looks up a point that may have stepped off the edge.
+    pub fn at(&self, point: Point<i64>) -> Option<&T> {
+        self.get_signed(point.x, point.y)
+    }
+
+    /// Position in the flat, row by row buffer.
+    pub fn index_of(&self, point: Point<usize>) -> Option<usize> {
+        if self.contains(point.x, point.y) {
+            Some(point.y * self.width + point.x)
+        } else {
+            None
+        }
+    }
+
+    /// Inverse of `index_of`, `None` past the last cell, as for every
+    /// index of a grid without columns.
+    pub fn point_of(&self, index: usize) -> Option<Point<usize>> {
+        if index < self.cells.len() {
+            Some(Point::new(index % self.width, index / self.width))
+        } else {
+            None
+        }
+    }
+
+    /// Moves from `point` by `offset`, as long as that stays on the grid.
+    pub fn step(&self, point: Point<usize>, offset: Point<i64>) -> Option<Point<usize>> {
+        point
+            .checked_add(offset)
+            .filter(|next| self.contains(next.x, next.y))
+    }
+
+    /// The cells reachable from `point` with one of the `offsets`.
+    pub fn neighbours<'a>(
+        &'a self,
+        point: Point<usize>,
+        offsets: &'a [Point<i64>],
+    ) -> impl Iterator<Item = Point<usize>> + 'a {
+        offsets.iter().filter_map(move |&offset| self.step(point, offset))
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cell, returning `false` when `(x, y)` is off the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.get_mut(x, y) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy for a grid without columns
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let start = if x < self.width { x } else { self.cells.len() };
+        self.cells[start..].iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every cell with its `(x, y)` position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Draws the grid one row per line, e.g. for debug logging.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows().take(self.height) {
+            out.extend(row.iter().map(&f));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Grid<char> {
+    pub fn from_chars(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input, "a character", Some)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &T {
+        &self[(point.x, point.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut T {
+        &mut self[(point.x, point.y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(|&ch| ch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "abcd\nefgh\nijkl\n";
+
+    #[test]
+    fn from_chars_keeps_non_square_shape() {
+        let grid = Grid::from_chars(SAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.get(3, 0), Some(&'d'));
+        assert_eq!(grid.get(0, 2), Some(&'i'));
+        assert_eq!(grid.get(4, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid[(2, 1)], 'g');
+    }
+
+    #[test]
+    fn parse_maps_characters_and_reports_unknown_ones() {
+        let wall = |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = Grid::parse("#.\n.#\n", "`#` or `.`", wall).unwrap();
+        assert_eq!(grid.render(|&wall| if wall { '#' } else { '.' }), "#.\n.#\n");
+
+        let error = Grid::parse("#.\n.x\n", "`#` or `.`", wall).unwrap_err();
+        assert_eq!(error.to_string(), "2:2: expected `#` or `.`, found `x`");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let error = Grid::from_chars("abc\nab\n").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.expected_token(), Some("a row of 3 cells"));
+    }
+
+    #[test]
+    fn rows_columns_and_cells() {
+        let grid = Grid::from_chars(SAMPLE).unwrap();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["abcd", "efgh", "ijkl"]);
+
+        let column: String = grid.column(1).collect();
+        assert_eq!(column, "bfj");
+        assert_eq!(grid.columns().count(), 4);
+
+        let (position, cell) = grid.cells().nth(6).unwrap();
+        assert_eq!((position, *cell), ((2, 1), 'g'));
+    }
+
+    #[test]
+    fn mutation_through_set_get_mut_and_index() {
+        let mut grid = Grid::new(3, 2, 0);
+        assert!(grid.set(2, 1, 5));
+        assert!(!grid.set(3, 1, 5));
+        *grid.get_mut(0, 0).unwrap() = 1;
+        grid[(1, 1)] += 2;
+        for ((x, _), cell) in grid.cells_mut() {
+            *cell += x as i32 * 10;
+        }
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1, 10, 20][..], &[0, 12, 25][..]]);
+    }
+
+    #[test]
+    fn from_rows_rejects_ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(grid.map(|n| n * 2)[(1, 1)], 8);
+    }
+
+    #[test]
+    fn points_index_and_step_within_bounds() {
+        use crate::point::ORTHOGONAL;
+
+        let grid = Grid::from_chars(SAMPLE).unwrap();
+        let corner = Point::new(3, 2);
+        assert_eq!(grid[corner], 'l');
+        assert_eq!(grid.at(Point::new(-1, 1)), None);
+        assert_eq!(grid.index_of(corner), Some(11));
+        assert_eq!(grid.point_of(6), Some(Point::new(2, 1)));
+        assert_eq!(grid.point_of(12), None);
+        assert_eq!(Grid::new(0, 3, '.').point_of(0), None);
+        assert_eq!(Grid::from_chars("").unwrap().point_of(0), None);
+
+        let mut around: Vec<_> = grid.neighbours(corner, &ORTHOGONAL).collect();
+        around.sort();
+        assert_eq!(around, vec![Point::new(2, 2), Point::new(3, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 4x3 grid")]
+    fn index_panics_outside_grid() {
+        let grid = Grid::from_chars(SAMPLE).unwrap();
+        let _ = grid[(4, 0)];
+    }
+}
@@ -0,0 +1,41 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The repository root, which holds a directory per year.
+pub fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Location of a day's puzzle input, relative to the repository root, or
+/// to the input cache.
+pub fn puzzle_input_path(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(year.to_string())
+        .join(format!("d{}", day))
+        .join("input.txt")
+}
+
+/// Reads the whole input into memory.
+pub fn read_to_string<P: AsRef<Path>>(file_path: P) -> io::Result<String> {
+    std::fs::read_to_string(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_input_path_points_into_year_directory() {
+        assert_eq!(
+            puzzle_input_path(2024, 12),
+            PathBuf::from("2024/d12/input.txt")
+        );
+    }
+
+    #[test]
+    fn read_to_string_fails_for_missing_file() {
+        assert!(read_to_string("./d0/missing.txt").is_err());
+    }
+}
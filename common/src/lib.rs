@@ -0,0 +1,41 @@
+//! Shared plumbing for the Advent of Code solutions.
+//!
+//! Every day used to carry its own copy of the logger setup, the input path
+//! and the file reading boilerplate. They live here now, together with the
+//! `Solution` trait, so a new day only has to write its parser and its tasks.
+
+pub mod alloc;
+pub mod answers;
+pub mod cancel;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod input;
+pub mod logger;
+#[cfg(any(test, feature = "mock-server"))]
+pub mod mock_server;
+pub mod output;
+pub mod params;
+pub mod pathfinding;
+pub mod parse;
+pub mod point;
+pub mod puzzle;
+pub mod solution;
+pub mod submit;
+
+pub use alloc::AllocStats;
+pub use answers::{answers_path, input_hash, AnswersError, KnownAnswer, KnownAnswers};
+pub use cancel::CancelToken;
+pub use error::ParseError;
+pub use examples::{fixtures_dir, Example};
+pub use fetch::{FetchError, FetchedInput, InputProvider};
+pub use grid::Grid;
+pub use input::{puzzle_input_path, read_to_string, repository_root};
+pub use logger::{init_logger, init_tracing, TraceGuard};
+pub use output::print_result;
+pub use params::Params;
+pub use point::{Direction, Point};
+pub use puzzle::{Parsed, Part, Puzzle};
+pub use solution::{Answer, Solution};
+pub use submit::{SubmitError, Verdict};
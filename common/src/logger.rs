@@ -0,0 +1,48 @@
+//! Diagnostics go through `tracing` and are written to stderr, so standard
+//! output only carries the answers. `LOG_LEVEL` takes the usual filter
+//! directives, e.g. `LOG_LEVEL=debug` or `LOG_LEVEL=day18=trace`.
+
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::prelude::*;
+
+/// Keeps the trace file open; it is completed when this is dropped.
+#[must_use = "the trace file is only written when the guard is dropped"]
+pub struct TraceGuard {
+    _flush: Option<FlushGuard>,
+}
+
+/// Initializes logging at `info`, overridable with the `LOG_LEVEL` variable.
+pub fn init_logger() {
+    // without a trace file there is nothing to flush
+    let _ = init_tracing(None);
+}
+
+/// Like `init_logger`, and with `chrome_trace` also records every span and
+/// `debug` event in a Chrome trace-event file, to open in Perfetto or
+/// `chrome://tracing`.
+pub fn init_tracing(chrome_trace: Option<&Path>) -> TraceGuard {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .with_env_var("LOG_LEVEL")
+        .from_env_lossy();
+    let stderr = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_filter(filter);
+
+    let (chrome, guard) = match chrome_trace {
+        Some(path) => {
+            let (layer, guard) = ChromeLayerBuilder::new().file(path).include_args(true).build();
+            (Some(layer.with_filter(LevelFilter::DEBUG)), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    // a second call keeps the first subscriber, as tests may do
+    let _ = tracing_subscriber::registry().with(stderr).with(chrome).try_init();
+    TraceGuard { _flush: guard }
+}
@@ -0,0 +1,42 @@
+use std::fmt::Display;
+
+fn ordinal(task: usize) -> String {
+    let suffix = match (task % 10, task % 100) {
+        (1, n) if n != 11 => "st",
+        (2, n) if n != 12 => "nd",
+        (3, n) if n != 13 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", task, suffix)
+}
+
+fn format_result<T: Display>(task: usize, label: &str, result: T) -> String {
+    if label.is_empty() {
+        format!("{} Task: {}", ordinal(task), result)
+    } else {
+        format!("{} Task: {}: {}", ordinal(task), label, result)
+    }
+}
+
+/// Prints a task's answer as `1st Task: <label>: <result>`.
+pub fn print_result<T: Display>(task: usize, label: &str, result: T) {
+    println!("{}", format_result(task, label, result));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_result_with_label() {
+        assert_eq!(
+            format_result(1, "Total distance is", 11),
+            "1st Task: Total distance is: 11"
+        );
+    }
+
+    #[test]
+    fn format_result_without_label() {
+        assert_eq!(format_result(2, "", "6,1"), "2nd Task: 6,1");
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// Puzzle parameters passed as `name=value` pairs, such as the size of
+/// day 14's space, which differ between the examples and the real input.
+///
+/// They also carry the strict flag: parsers that skip lines they can't read
+/// report those lines as errors instead when it is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+    strict: bool,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.values.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Builds parameters from `name=value` strings.
+    pub fn from_pairs<S: AsRef<str>>(pairs: &[S]) -> Result<Self, ParseError> {
+        let mut params = Params::new();
+        for pair in pairs {
+            let pair = pair.as_ref();
+            match pair.split_once('=') {
+                Some((name, value)) if !name.trim().is_empty() => {
+                    params = params.with(name.trim(), value.trim());
+                }
+                _ => {
+                    return Err(ParseError::new(format!(
+                        "parameter `{}` is not in the name=value form",
+                        pair
+                    )))
+                }
+            }
+        }
+        Ok(params)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Value of `name` converted to `T`, or `None` when it wasn't given.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, ParseError> {
+        match self.values.get(name) {
+            Some(value) => value.parse().map(Some).map_err(|_| {
+                ParseError::new(format!("invalid value `{}` for parameter `{}`", value, name))
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_pairs_reads_name_value_pairs() {
+        let params = Params::from_pairs(&["wide=11", "tall = 7"]).unwrap();
+        assert_eq!(params.get::<usize>("wide"), Ok(Some(11)));
+        assert_eq!(params.get::<usize>("tall"), Ok(Some(7)));
+        assert_eq!(params.get::<usize>("deep"), Ok(None));
+    }
+
+    #[test]
+    fn from_pairs_rejects_missing_value_separator() {
+        assert!(Params::from_pairs(&["wide"]).is_err());
+        assert!(Params::from_pairs(&["=11"]).is_err());
+    }
+
+    #[test]
+    fn strict_is_off_unless_asked_for() {
+        assert!(!Params::new().is_strict());
+        assert!(Params::new().strict(true).is_strict());
+        assert!(Params::new().strict(true).names().next().is_none());
+    }
+
+    #[test]
+    fn get_rejects_value_of_wrong_type() {
+        let params = Params::new().with("wide", "eleven");
+        assert!(params.get::<usize>("wide").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Helpers that let the day parsers report where their input went wrong.
+
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// Lines of the input paired with their 1-based line number.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(index, line)| (index + 1, line))
+}
+
+/// 1-based column at which `token`, a slice of `line`, starts.
+pub fn column(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(line.as_ptr() as usize)
+        .filter(|&offset| offset <= line.len())
+        .unwrap_or(0);
+    line[..offset].chars().count() + 1
+}
+
+/// Parses `token`, a slice of `line`, or says which `expected` token is missing.
+pub fn parse_token<T: FromStr>(
+    line_number: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::expected(line_number, column(line, token), expected, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbered_lines_start_at_one() {
+        let lines: Vec<_> = numbered_lines("a\nb\n").collect();
+        assert_eq!(lines, vec![(1, "a"), (2, "b")]);
+    }
+
+    #[test]
+    fn column_of_token_within_line() {
+        let line = "47|5x";
+        let token = line.split('|').nth(1).unwrap();
+        assert_eq!(column(line, token), 4);
+    }
+
+    #[test]
+    fn parse_token_reports_position_of_bad_token() {
+        let line = "190: 10 1x";
+        let token = line.split_whitespace().nth(2).unwrap();
+        let error = parse_token::<i64>(4, line, token, "number").unwrap_err();
+        assert_eq!(error.to_string(), "4:9: expected number, found `1x`");
+    }
+}
@@ -0,0 +1,262 @@
+//! Searches over anything that can list the neighbours of a node.
+//!
+//! The searches take the neighbours as a closure, so they work the same for a
+//! `Grid`, a graph in a `HashMap` or a state space that is never stored at all.
+//! `grid_path` covers the common case of walking a map cell by cell.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::grid::Grid;
+use crate::point::{Point, ORTHOGONAL};
+
+/// A route found by one of the searches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N: Eq + Hash> {
+    /// Every node from the start to the goal, both included.
+    pub route: Vec<N>,
+    /// Total cost of the route, the number of steps for `bfs`.
+    pub cost: u64,
+    /// Best known cost of every node the search reached before it stopped.
+    pub distances: HashMap<N, u64>,
+}
+
+impl<N: Copy + Eq + Hash> Path<N> {
+    fn new(goal: N, cost: u64, parents: &HashMap<N, N>, distances: HashMap<N, u64>) -> Self {
+        let mut route = vec![goal];
+        let mut node = goal;
+        while let Some(&parent) = parents.get(&node) {
+            route.push(parent);
+            node = parent;
+        }
+        route.reverse();
+        Path {
+            route,
+            cost,
+            distances,
+        }
+    }
+
+    /// Number of moves along the route.
+    pub fn steps(&self) -> usize {
+        self.route.len() - 1
+    }
+
+    pub fn start(&self) -> N {
+        self.route[0]
+    }
+
+    pub fn goal(&self) -> N {
+        self.route[self.route.len() - 1]
+    }
+}
+
+/// Breadth-first search where every move costs one.
+pub fn bfs<N, F, I, G>(start: N, mut neighbours: F, mut is_goal: G) -> Option<Path<N>>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        if is_goal(&node) {
+            return Some(Path::new(node, distance, &parents, distances));
+        }
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(distance + 1);
+                parents.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest route when moves carry their own, non-negative, cost.
+pub fn dijkstra<N, F, I, G>(start: N, neighbours: F, is_goal: G) -> Option<Path<N>>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// Dijkstra guided by `heuristic`, an estimate of the remaining cost.
+///
+/// The route is only guaranteed to be the cheapest one when the heuristic
+/// never overestimates, e.g. the Manhattan distance on a grid.
+pub fn astar<N, F, I, H, G>(start: N, mut neighbours: F, mut heuristic: H, mut is_goal: G) -> Option<Path<N>>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+    G: FnMut(&N) -> bool,
+{
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut parents = HashMap::new();
+    // the heap holds indices into `nodes`, so `N` does not have to be `Ord`
+    let mut nodes = vec![start];
+    let mut open = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    while let Some(Reverse((_, cost, id))) = open.pop() {
+        let node = nodes[id];
+        if cost > distances[&node] {
+            // a cheaper way to this node was queued after this one
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(Path::new(node, cost, &parents, distances));
+        }
+        for (next, weight) in neighbours(&node) {
+            let next_cost = cost + weight;
+            if distances.get(&next).is_some_and(|&known| known <= next_cost) {
+                continue;
+            }
+            distances.insert(next, next_cost);
+            parents.insert(next, node);
+            nodes.push(next);
+            open.push(Reverse((next_cost + heuristic(&next), next_cost, nodes.len() - 1)));
+        }
+    }
+    None
+}
+
+/// Every node reachable from `start`, with the number of steps to it.
+pub fn flood_fill<N, F, I>(start: N, mut neighbours: F) -> HashMap<N, u64>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Shortest walk between two cells, stepping up, down, left and right onto
+/// cells that are `passable`.
+pub fn grid_path<T, P>(grid: &Grid<T>, from: Point<usize>, to: Point<usize>, passable: P) -> Option<Path<Point<usize>>>
+where
+    P: Fn(&T) -> bool,
+{
+    astar(
+        from,
+        |&point| {
+            grid.neighbours(point, &ORTHOGONAL)
+                .filter(|&next| passable(&grid[next]))
+                .map(|next| (next, 1))
+        },
+        |point| point.manhattan(to) as u64,
+        |&point| point == to,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+..#....
+..#.##.
+....#..
+###.###
+....#..
+";
+
+    fn open(ch: char) -> Option<bool> {
+        match ch {
+            '.' => Some(true),
+            '#' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn bfs_reconstructs_route() {
+        let grid = Grid::parse(MAZE, "`.` or `#`", open).unwrap();
+        let goal = Point::new(6, 0);
+        let path = bfs(
+            Point::new(0, 0),
+            |&point| grid.neighbours(point, &ORTHOGONAL).filter(|&next| grid[next]),
+            |&point| point == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.steps(), 10);
+        assert_eq!(path.cost, 10);
+        assert_eq!((path.start(), path.goal()), (Point::new(0, 0), goal));
+        for pair in path.route.windows(2) {
+            assert_eq!(pair[0].manhattan(pair[1]), 1);
+            assert!(grid[pair[1]]);
+        }
+        assert_eq!(path.distances[&Point::new(3, 2)], 5);
+    }
+
+    #[test]
+    fn searches_agree_on_grid() {
+        let grid = Grid::parse(MAZE, "`.` or `#`", open).unwrap();
+        let to = Point::new(6, 2);
+        let by_astar = grid_path(&grid, Point::new(0, 0), to, |&cell| cell).unwrap();
+        let by_dijkstra = dijkstra(
+            Point::new(0, 0),
+            |&point| grid.neighbours(point, &ORTHOGONAL).filter(|&next| grid[next]).map(|next| (next, 1)),
+            |&point| point == to,
+        )
+        .unwrap();
+
+        assert_eq!(by_astar.cost, 12);
+        assert_eq!(by_dijkstra.cost, 12);
+        assert_eq!(grid_path(&grid, Point::new(0, 0), Point::new(6, 4), |&cell| cell), None);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_detour() {
+        let edges: HashMap<char, Vec<(char, u64)>> = HashMap::from([
+            ('a', vec![('b', 7), ('c', 2)]),
+            ('c', vec![('d', 2)]),
+            ('d', vec![('b', 1)]),
+        ]);
+        let path = dijkstra(
+            'a',
+            |node| edges.get(node).cloned().unwrap_or_default(),
+            |&node| node == 'b',
+        )
+        .unwrap();
+
+        assert_eq!(path.route, vec!['a', 'c', 'd', 'b']);
+        assert_eq!(path.cost, 5);
+    }
+
+    #[test]
+    fn flood_fill_reaches_connected_cells_only() {
+        let grid = Grid::parse(MAZE, "`.` or `#`", open).unwrap();
+        let region = flood_fill(Point::new(0, 4), |&point| {
+            grid.neighbours(point, &ORTHOGONAL).filter(|&next| grid[next])
+        });
+
+        assert_eq!(region.len(), 21);
+        assert!(!region.contains_key(&Point::new(6, 4)));
+    }
+}
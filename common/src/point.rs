@@ -0,0 +1,259 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on a plane, with `y` growing downwards like the rows
+/// of a puzzle map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Number of orthogonal steps between the two points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Number of king moves between the two points.
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Neg<Output = T>> Point<T> {
+    /// Quarter turn clockwise as drawn on the map, so up becomes right.
+    pub fn rotate_right(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Quarter turn counter-clockwise as drawn on the map, so up becomes left.
+    pub fn rotate_left(self) -> Self {
+        Point::new(self.y, -self.x)
+    }
+}
+
+impl Point<usize> {
+    pub fn signed(self) -> Point<i64> {
+        Point::new(self.x as i64, self.y as i64)
+    }
+
+    /// Moves by `offset`, or `None` when that would leave the first quadrant.
+    pub fn checked_add(self, offset: Point<i64>) -> Option<Point<usize>> {
+        (self.signed() + offset).unsigned()
+    }
+}
+
+impl Point<i64> {
+    /// The same point as grid coordinates, if neither coordinate is negative.
+    pub fn unsigned(self) -> Option<Point<usize>> {
+        if self.x >= 0 && self.y >= 0 {
+            Some(Point::new(self.x as usize, self.y as usize))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Point::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// Written the way puzzle inputs list coordinates, e.g. `6,1`.
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// The four steps along a row or a column.
+pub const ORTHOGONAL: [Point<i64>; 4] = [
+    Point::new(0, -1),
+    Point::new(1, 0),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+];
+
+/// The four diagonal steps.
+pub const DIAGONAL: [Point<i64>; 4] = [
+    Point::new(-1, -1),
+    Point::new(1, -1),
+    Point::new(1, 1),
+    Point::new(-1, 1),
+];
+
+/// All eight steps to the surrounding cells.
+pub const NEIGHBOURS: [Point<i64>; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`, in the same order as `ORTHOGONAL`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn offset(self) -> Point<i64> {
+        ORTHOGONAL[self as usize]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Parses the arrows and letters puzzles use for moves.
+    pub fn from_char(ch: char) -> Option<Direction> {
+        match ch {
+            '^' | 'U' | 'N' => Some(Direction::Up),
+            '>' | 'R' | 'E' => Some(Direction::Right),
+            'v' | 'D' | 'S' => Some(Direction::Down),
+            '<' | 'L' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 4);
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a - b, Point::new(2, -6));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(3, 1));
+    }
+
+    #[test]
+    fn distances_work_for_signed_and_unsigned() {
+        assert_eq!(Point::new(1, 5).manhattan(Point::new(4, 1)), 7);
+        assert_eq!(Point::new(1, 5).chebyshev(Point::new(4, 1)), 4);
+        assert_eq!(Point::<usize>::new(6, 1).manhattan(Point::new(0, 0)), 7);
+        assert_eq!(Point::new(-2, 0).chebyshev(Point::new(2, -1)), 4);
+    }
+
+    #[test]
+    fn rotation_follows_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.offset().rotate_right(), direction.turn_right().offset());
+            assert_eq!(direction.offset().rotate_left(), direction.turn_left().offset());
+            assert_eq!(-direction.offset(), direction.opposite().offset());
+        }
+        assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+    }
+
+    #[test]
+    fn converts_between_signed_and_grid_points() {
+        let start = Point::<usize>::new(0, 2);
+        assert_eq!(start.checked_add(Direction::Right.offset()), Some(Point::new(1, 2)));
+        assert_eq!(start.checked_add(Direction::Left.offset()), None);
+        assert_eq!(Point::new(-1, 0).unsigned(), None);
+        assert_eq!(<(usize, usize)>::from(start), (0, 2));
+        assert_eq!(start.to_string(), "0,2");
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::params::Params;
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: usize) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A parsed input whose concrete type has been erased, so the runner can
+/// answer any day's tasks without knowing its `Input`.
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Answer;
+}
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S> Parsed for ParsedInput<S>
+where
+    S: Solution,
+    S::Input: Send + Sync,
+{
+    fn solve(&self, part: Part) -> Answer {
+        match part {
+            Part::One => S::part1(&self.0),
+            Part::Two => S::part2(&self.0),
+        }
+    }
+}
+
+type ParseFn = fn(&str, &Params) -> Result<Box<dyn Parsed>, ParseError>;
+
+fn parse_boxed<S>(input: &str, params: &Params) -> Result<Box<dyn Parsed>, ParseError>
+where
+    S: Solution + 'static,
+    S::Input: Send + Sync,
+{
+    Ok(Box::new(ParsedInput::<S>(S::parse_with(input, params)?)))
+}
+
+/// A registered day: which puzzle it solves and how to drive its `Solution`.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub params: &'static [&'static str],
+    parse: ParseFn,
+}
+
+impl Puzzle {
+    pub fn new<S>(year: u32, day: u32) -> Self
+    where
+        S: Solution + 'static,
+        S::Input: Send + Sync,
+    {
+        Puzzle {
+            year,
+            day,
+            params: S::PARAMS,
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    /// Parses the input, refusing parameters the day doesn't know about.
+    pub fn parse(&self, input: &str, params: &Params) -> Result<Box<dyn Parsed>, ParseError> {
+        if let Some(unknown) = params.names().find(|name| !self.params.contains(name)) {
+            return Err(ParseError::new(format!(
+                "unknown parameter `{}` (known: {})",
+                unknown,
+                if self.params.is_empty() {
+                    "none".to_string()
+                } else {
+                    self.params.join(", ")
+                }
+            )));
+        }
+        (self.parse)(input, params)
+    }
+}
+
+impl fmt::Debug for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Puzzle")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Scaled;
+
+    impl Solution for Scaled {
+        type Input = (Vec<i64>, i64);
+
+        const PARAMS: &'static [&'static str] = &["factor"];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Self::parse_with(input, &Params::new())
+        }
+
+        fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+            let numbers = input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| ParseError::new("not a number")))
+                .collect::<Result<_, _>>()?;
+            Ok((numbers, params.get("factor")?.unwrap_or(1)))
+        }
+
+        fn part1((numbers, factor): &Self::Input) -> Answer {
+            (numbers.iter().sum::<i64>() * factor).into()
+        }
+    }
+
+    #[test]
+    fn puzzle_solves_both_parts_through_erased_input() {
+        let puzzle = Puzzle::new::<Scaled>(2024, 1);
+        let parsed = puzzle.parse("1 2 3", &Params::new()).unwrap();
+        assert_eq!(parsed.solve(Part::One), Answer::Number(6));
+        assert_eq!(parsed.solve(Part::Two), Answer::Unsolved);
+    }
+
+    #[test]
+    fn puzzle_passes_known_params_to_the_solution() {
+        let puzzle = Puzzle::new::<Scaled>(2024, 1);
+        let parsed = puzzle
+            .parse("1 2 3", &Params::new().with("factor", 10))
+            .unwrap();
+        assert_eq!(parsed.solve(Part::One), Answer::Number(60));
+    }
+
+    #[test]
+    fn puzzle_rejects_unknown_params() {
+        let puzzle = Puzzle::new::<Scaled>(2024, 1);
+        assert!(puzzle.parse("1", &Params::new().with("size", 3)).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::params::Params;
+
+/// A task's result. Days answer with integers of any width or with text
+/// (day 18 reports a coordinate), so they all collapse into this.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Calling convention shared by every day: parse the raw input once, then
+/// answer both tasks from the parsed form.
+pub trait Solution {
+    type Input;
+
+    /// Names of the puzzle parameters `parse_with` understands.
+    const PARAMS: &'static [&'static str] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Parses the input with parameter overrides. Days that take no
+    /// parameters keep the default, which ignores them.
+    fn parse_with(input: &str, _params: &Params) -> Result<Self::Input, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_displays_wide_integers_and_text() {
+        assert_eq!(Answer::from(i128::MAX).to_string(), i128::MAX.to_string());
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from(-3i32).to_string(), "-3");
+        assert_eq!(Answer::from("6,1").to_string(), "6,1");
+    }
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Input = Vec<usize>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.lines().map(str::len).collect())
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().sum::<usize>().into()
+        }
+    }
+
+    #[test]
+    fn part2_defaults_to_unsolved() {
+        let input = Lengths::parse("ab\ncde").unwrap();
+        assert_eq!(Lengths::part1(&input), Answer::Number(5));
+        assert_eq!(Lengths::part2(&input), Answer::Unsolved);
+    }
+}
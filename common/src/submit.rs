@@ -0,0 +1,375 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::fetch::{agent, InputProvider};
+use crate::puzzle::Part;
+
+/// What the website said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answered too soon after the previous attempt; try again after the wait.
+    RateLimited(Duration),
+    /// The part was already solved, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page returned for a submitted answer.
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited(wait_time(&text).unwrap_or_default()))
+        } else if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the website checked the answer and turned it down.
+    pub fn is_rejection(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+
+    fn token(self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::RateLimited(_) | Verdict::AlreadySolved => None,
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Verdict> {
+        match token {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, without the markup.
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for ch in article.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parses "You have 1h 4m 32s left to wait".
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let (number, unit) = amount.split_at(amount.len() - 1);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    #[error("{answer} was already submitted for part {part} and was {verdict}")]
+    AlreadyRejected {
+        part: Part,
+        answer: String,
+        verdict: Verdict,
+    },
+    #[error("part {part} was already solved with {answer}")]
+    AlreadySolved { part: Part, answer: String },
+    #[error("{answer} can't be right, {earlier} was already {verdict}")]
+    OutOfBounds {
+        answer: String,
+        earlier: String,
+        verdict: Verdict,
+    },
+    #[error("AOC_SESSION is not set, so answers can't be submitted")]
+    NoSession,
+    #[error("can't post to {url}: {message}")]
+    Post { url: String, message: String },
+    #[error("can't tell the verdict from the response page:\n{0}")]
+    UnknownResponse(String),
+    #[error("can't access {path}: {source}")]
+    History {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Answers already submitted for one day, kept next to its cached input so
+/// a rejected answer is never posted twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Submissions {
+    entries: Vec<(Part, String, Verdict)>,
+}
+
+impl Submissions {
+    pub fn load(path: &Path) -> Result<Submissions, SubmitError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Submissions::default()),
+            Err(source) => {
+                return Err(SubmitError::History {
+                    path: path.display().to_string(),
+                    source,
+                })
+            }
+        };
+        let entries = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let part = fields.next()?.parse().ok().and_then(Part::from_number)?;
+                let answer = fields.next()?.to_string();
+                let verdict = Verdict::from_token(fields.next()?)?;
+                Some((part, answer, verdict))
+            })
+            .collect();
+        Ok(Submissions { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        let history_error = |source| SubmitError::History {
+            path: path.display().to_string(),
+            source,
+        };
+        let mut text = String::new();
+        for (part, answer, verdict) in &self.entries {
+            if let Some(token) = verdict.token() {
+                text.push_str(&format!("{}\t{}\t{}\n", part, answer, token));
+            }
+        }
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(history_error)?;
+        }
+        fs::write(path, text).map_err(history_error)
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) {
+        if verdict.token().is_some() {
+            self.entries.push((part, answer.to_string(), verdict));
+        }
+    }
+
+    /// Refuses answers the earlier verdicts already rule out, including
+    /// numbers on the wrong side of a "too high" or "too low".
+    pub fn check(&self, part: Part, answer: &str) -> Result<(), SubmitError> {
+        let number = answer.parse::<i128>().ok();
+        for (earlier_part, earlier, verdict) in &self.entries {
+            if *earlier_part != part {
+                continue;
+            }
+            if *verdict == Verdict::Correct {
+                return Err(SubmitError::AlreadySolved {
+                    part,
+                    answer: earlier.clone(),
+                });
+            }
+            if earlier == answer {
+                return Err(SubmitError::AlreadyRejected {
+                    part,
+                    answer: answer.to_string(),
+                    verdict: *verdict,
+                });
+            }
+            let ruled_out = match (number, earlier.parse::<i128>(), verdict) {
+                (Some(number), Ok(earlier), Verdict::TooHigh) => number > earlier,
+                (Some(number), Ok(earlier), Verdict::TooLow) => number < earlier,
+                _ => false,
+            };
+            if ruled_out {
+                return Err(SubmitError::OutOfBounds {
+                    answer: answer.to_string(),
+                    earlier: earlier.clone(),
+                    verdict: *verdict,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl InputProvider {
+    /// Where the answers submitted for a day are remembered.
+    pub fn submissions_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_path(year, day).with_file_name("submissions.txt")
+    }
+
+    /// Posts the answer of one part, unless an earlier verdict rules it out.
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: &str) -> Result<Verdict, SubmitError> {
+        let history = self.submissions_path(year, day);
+        let mut submissions = Submissions::load(&history)?;
+        submissions.check(part, answer)?;
+
+        let session = self.session.as_deref().ok_or(SubmitError::NoSession)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let failed = |message: String| SubmitError::Post {
+            url: url.clone(),
+            message,
+        };
+        let page = agent()
+            .post(&url)
+            .set("Cookie", &format!("session={}", session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| failed(e.to_string()))?
+            .into_string()
+            .map_err(|e| failed(e.to_string()))?;
+
+        let verdict = Verdict::from_page(&page).ok_or_else(|| SubmitError::UnknownResponse(article_text(&page)))?;
+        if verdict.token().is_some() {
+            submissions.record(part, answer, verdict);
+            submissions.save(&history)?;
+        }
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+    use std::env;
+
+    const CORRECT: &str = include_str!("../fixtures/submit/correct.html");
+    const TOO_HIGH: &str = include_str!("../fixtures/submit/too_high.html");
+    const TOO_LOW: &str = include_str!("../fixtures/submit/too_low.html");
+    const WRONG: &str = include_str!("../fixtures/submit/wrong.html");
+    const RATE_LIMITED: &str = include_str!("../fixtures/submit/rate_limited.html");
+    const ALREADY_SOLVED: &str = include_str!("../fixtures/submit/already_solved.html");
+
+    #[test]
+    fn verdict_from_recorded_pages() {
+        assert_eq!(Verdict::from_page(CORRECT), Some(Verdict::Correct));
+        assert_eq!(Verdict::from_page(TOO_HIGH), Some(Verdict::TooHigh));
+        assert_eq!(Verdict::from_page(TOO_LOW), Some(Verdict::TooLow));
+        assert_eq!(Verdict::from_page(WRONG), Some(Verdict::Wrong));
+        assert_eq!(
+            Verdict::from_page(RATE_LIMITED),
+            Some(Verdict::RateLimited(Duration::from_secs(272)))
+        );
+        assert_eq!(Verdict::from_page(ALREADY_SOLVED), Some(Verdict::AlreadySolved));
+        assert_eq!(Verdict::from_page("<html><body>Maintenance</body></html>"), None);
+    }
+
+    #[test]
+    fn wait_time_reads_every_unit() {
+        assert_eq!(wait_time("You have 37s left to wait."), Some(Duration::from_secs(37)));
+        assert_eq!(wait_time("You have 1h 2m 3s left to wait."), Some(Duration::from_secs(3723)));
+        assert_eq!(wait_time("Please wait one minute."), None);
+    }
+
+    #[test]
+    fn check_rules_out_rejected_and_bounded_answers() {
+        let mut submissions = Submissions::default();
+        submissions.record(Part::One, "500", Verdict::TooHigh);
+        submissions.record(Part::One, "100", Verdict::TooLow);
+        submissions.record(Part::One, "250", Verdict::Wrong);
+        submissions.record(Part::One, "300", Verdict::RateLimited(Duration::from_secs(5)));
+
+        assert!(matches!(submissions.check(Part::One, "250"), Err(SubmitError::AlreadyRejected { .. })));
+        assert!(matches!(submissions.check(Part::One, "501"), Err(SubmitError::OutOfBounds { .. })));
+        assert!(matches!(submissions.check(Part::One, "99"), Err(SubmitError::OutOfBounds { .. })));
+        assert!(submissions.check(Part::One, "300").is_ok());
+        assert!(submissions.check(Part::Two, "250").is_ok());
+
+        submissions.record(Part::One, "300", Verdict::Correct);
+        assert_eq!(
+            submissions.check(Part::One, "301").unwrap_err().to_string(),
+            "part 1 was already solved with 300"
+        );
+    }
+
+    #[test]
+    fn submit_posts_the_form_and_refuses_to_repeat_a_rejection() {
+        let server = MockServer::start(|request| match request.body.as_str() {
+            "level=1&answer=11" => (200, TOO_LOW.to_string()),
+            "level=1&answer=42" => (200, CORRECT.to_string()),
+            _ => (200, WRONG.to_string()),
+        });
+        let cache = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        let provider = InputProvider::default()
+            .base_url(server.url())
+            .session("secret")
+            .cache_dir(&cache);
+
+        assert_eq!(provider.submit(2024, 1, Part::One, "11").unwrap(), Verdict::TooLow);
+        let error = provider.submit(2024, 1, Part::One, "11").unwrap_err();
+        assert_eq!(error.to_string(), "11 was already submitted for part 1 and was too low");
+        assert_eq!(provider.submit(2024, 1, Part::One, "42").unwrap(), Verdict::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2024/day/1/answer");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+        assert_eq!(
+            fs::read_to_string(provider.submissions_path(2024, 1)).unwrap(),
+            "1\t11\ttoo-low\n1\t42\tcorrect\n"
+        );
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn submit_needs_a_session() {
+        let cache = env::temp_dir().join(format!("aoc-submit-anonymous-{}", std::process::id()));
+        let error = InputProvider::default()
+            .cache_dir(&cache)
+            .submit(2024, 1, Part::One, "1")
+            .unwrap_err();
+        assert!(matches!(error, SubmitError::NoSession));
+    }
+}